@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs};
+use common::input::Input;
+
+/*
+environment variables configuring the cache: the session cookie of the account whose inputs we want,
+the site to fetch them from and the directory to keep them in
+ */
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2023";
+
+const USER_AGENT: &str = concat!("aoc2023-runner/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/*
+puzzle inputs kept as dir/dayN.txt, fetched from base_url/day/N/input the first time they are needed
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputCache {
+    pub dir: PathBuf,
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl InputCache {
+    /*
+    configured from AOC_CACHE_DIR (by default $XDG_CACHE_HOME/aoc2023 or ~/.cache/aoc2023), AOC_BASE_URL and AOC_SESSION
+     */
+    pub fn from_env() -> Result<InputCache, String> {
+        let var = |name: &str| env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let dir = var(CACHE_DIR_VAR).map(PathBuf::from)
+            .or_else(|| var("XDG_CACHE_HOME").map(|dir| PathBuf::from(dir).join("aoc2023")))
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".cache").join("aoc2023")))
+            .ok_or_else(|| format!("no directory for the input cache: set {CACHE_DIR_VAR}"))?;
+        let base_url = var(BASE_URL_VAR).unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(InputCache { dir, base_url, session: var(SESSION_VAR) })
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    pub fn cached(&self, day: u8) -> Option<Input> {
+        let path = self.path(day);
+        fs::read_to_string(&path).ok().map(|text| Input { name: path.display().to_string(), text })
+    }
+
+    /*
+    the cached input, fetched and stored first when it is not there yet
+     */
+    pub fn load(&self, day: u8) -> Result<Input, String> {
+        if let Some(input) = self.cached(day) {
+            return Ok(input);
+        }
+        let text = self.fetch(day)?;
+        let path = self.path(day);
+        // written next to it and renamed, so an interrupted write never looks cached
+        let partial = path.with_extension("part");
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&partial, &text))
+            .and_then(|_| fs::rename(&partial, &path))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        Ok(Input { name: path.display().to_string(), text })
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<String, String> {
+        let session = self.session.as_deref()
+            .ok_or_else(|| format!("day{day} input is not cached and {SESSION_VAR} is not set to fetch it"))?;
+        let url = format!("{}/day/{day}/input", self.base_url.trim_end_matches('/'));
+        let response = agent().get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .call()
+            .map_err(|e| describe_error(&url, day, e))?;
+        response.into_string().map_err(|e| format!("{url}: {e}"))
+    }
+}
+
+pub(crate) fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(TIMEOUT).user_agent(USER_AGENT).build()
+}
+
+pub(crate) fn describe_error(url: &str, day: u8, e: ureq::Error) -> String {
+    match e {
+        ureq::Error::Status(404, _) => format!("{url}: day{day} is not unlocked yet"),
+        ureq::Error::Status(code @ (400 | 401 | 403 | 500), _) => format!("{url}: session in {SESSION_VAR} was refused ({code})"),
+        ureq::Error::Status(code, _) => format!("{url}: server answered {code}"),
+        ureq::Error::Transport(e) => format!("{url}: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use super::*;
+
+    // path and cookie of each request the stub server got
+    type Requests = Arc<Mutex<Vec<(String, String)>>>;
+
+    /*
+    serves day 1 under /2023 on a loopback port, recording the path and cookie of every request
+     */
+    fn stub_server() -> (String, Requests) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2023", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let seen = requests.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let cookie = request.headers().iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string())
+                    .unwrap_or_default();
+                seen.lock().unwrap().push((request.url().to_string(), cookie));
+                let response = match request.url() {
+                    "/2023/day/1/input" => tiny_http::Response::from_string("1abc2\npqr3stu8vwx\n"),
+                    _ => tiny_http::Response::from_string("Not Found").with_status_code(404),
+                };
+                let _ = request.respond(response);
+            }
+        });
+        (base_url, requests)
+    }
+
+    fn temp_cache(name: &str, base_url: &str, session: Option<&str>) -> InputCache {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        InputCache { dir, base_url: base_url.to_string(), session: session.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_fetch_once_then_cached() {
+        let (base_url, requests) = stub_server();
+        let cache = temp_cache("fetch", &base_url, Some("53616c7465"));
+        let input = cache.load(1).unwrap();
+        assert_eq!(input.text, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(input.name, cache.path(1).display().to_string());
+        assert_eq!(fs::read_to_string(cache.path(1)).unwrap(), input.text);
+        assert_eq!(cache.load(1).unwrap(), input);
+        assert_eq!(*requests.lock().unwrap(), vec![("/2023/day/1/input".to_string(), "session=53616c7465".to_string())]);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_not_unlocked() {
+        let (base_url, _) = stub_server();
+        let cache = temp_cache("locked", &format!("{base_url}/"), Some("53616c7465"));
+        assert_eq!(cache.load(25), Err(format!("{base_url}/day/25/input: day25 is not unlocked yet")));
+        assert!(cache.cached(25).is_none());
+    }
+
+    #[test]
+    fn test_no_session() {
+        let (base_url, requests) = stub_server();
+        let cache = temp_cache("anonymous", &base_url, None);
+        assert_eq!(cache.load(1), Err("day1 input is not cached and AOC_SESSION is not set to fetch it".to_string()));
+        assert!(requests.lock().unwrap().is_empty());
+    }
+}
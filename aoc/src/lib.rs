@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use common::answers::Answers;
+use common::input::{self, Input};
+use common::Solver;
+use inputs::InputCache;
+
+pub mod inputs;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+pub mod timing;
+pub mod watch;
+
+pub const SOLVERS: [&dyn Solver; 12] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().find(|solver| solver.day() == day).copied()
+}
+
+/*
+source directory of a day crate in this workspace, where its input.txt and answers.txt live
+ */
+pub fn day_dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day{day}"))
+}
+
+/*
+the day's own puzzle input: from the input cache, its src/input.txt, embedded, or else fetched into the cache
+ */
+pub fn real_input(solver: &dyn Solver) -> Result<Input, String> {
+    day_input(solver.day(), solver.embedded_input())
+}
+
+/*
+real_input for any day crate in the workspace, including ones the runner is not built with
+ */
+pub fn day_input(day: u8, embedded: Option<&'static str>) -> Result<Input, String> {
+    let cache = InputCache::from_env();
+    if let Some(input) = cache.as_ref().ok().and_then(|cache| cache.cached(day)) {
+        return Ok(input);
+    }
+    let path = day_dir(day).join("src/input.txt");
+    input::load(Some(&path.to_string_lossy()), None)
+        .or_else(|_| input::load(None, embedded))
+        .or_else(|_| cache.and_then(|cache| cache.load(day)))
+}
+
+/*
+recorded facts about a day's answers, empty when the day has no answers.txt yet
+ */
+pub fn known_answers(day: u8) -> Result<Answers, String> {
+    read_answers(&answers_path(day))
+}
+
+pub fn answers_path(day: u8) -> PathBuf {
+    day_dir(day).join("answers.txt")
+}
+
+pub fn read_answers(path: &Path) -> Result<Answers, String> {
+    match std::fs::read_to_string(path) {
+        Ok(source) => Answers::parse(&source).map_err(|e| e.render(&path.display().to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Part;
+    use super::*;
+
+    #[test]
+    fn test_solvers_in_day_order() {
+        SOLVERS.iter().enumerate().for_each(|(idx, solver)| {
+            assert_eq!(solver.day() as usize, idx + 1);
+        });
+    }
+
+    #[test]
+    fn test_solve_day6() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let day6 = solver(6).unwrap();
+        assert_eq!(day6.solve(input, Part::One), Ok("288".to_string()));
+        assert_eq!(day6.solve(input, Part::Two), Ok("71503".to_string()));
+    }
+
+    #[test]
+    fn test_solve_day7() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+        let day7 = solver(7).unwrap();
+        assert_eq!(day7.solve(input, Part::One), Ok("6440".to_string()));
+        assert_eq!(day7.solve(input, Part::Two), Ok("5905".to_string()));
+    }
+
+    #[test]
+    fn test_solver_unknown() {
+        assert!(solver(13).is_none());
+    }
+}
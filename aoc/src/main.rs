@@ -0,0 +1,150 @@
+use std::time::Duration;
+use std::{env, process};
+use aoc::inputs::InputCache;
+use aoc::report::Report;
+use aoc::submit::{Submitter, Verdict};
+use common::{input, Part, Solver};
+
+const USAGE: &str = "\
+usage: aoc [--json] <day> <part> [<input file> | -]
+       aoc bench [<day>...]
+       aoc fetch <day>...
+       aoc submit <day> <part> [<answer>]
+       aoc new <day>
+       aoc watch <day>";
+
+// runs per day in bench mode, the median is reported
+const SAMPLES: usize = 11;
+
+// how often watch mode looks for changed files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn main() {
+    common::logging::init();
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let json = args.first().is_some_and(|arg| arg == "--json");
+    if json {
+        args.remove(0);
+    }
+    let (day, part, path) = match args.as_slice() {
+        [command, days @ ..] if command == "bench" => return bench(days),
+        [command, days @ ..] if command == "fetch" && !days.is_empty() => return fetch(days),
+        [command, day] if command == "new" => return new_day(day),
+        [command, day] if command == "watch" => return watch(day),
+        [command, day, part, answer @ ..] if command == "submit" && answer.len() <= 1 => return submit(day, part, answer.first()),
+        [day, part] => (day, part, None),
+        [day, part, path] => (day, part, Some(path.as_str())),
+        _ => fail(USAGE),
+    };
+    let solver = parse_day(day);
+    let part = parse_part(part);
+
+    let input = match path {
+        Some(path) => input::load(Some(path), None),
+        None => aoc::real_input(solver),
+    }.unwrap_or_else(|e| fail(&e));
+    match solver.run(&input.text, part) {
+        Ok(run) if json => println!("{}", Report::new(solver.day(), part, run).to_json()),
+        Ok(run) => println!("day{} part{part}: {}", solver.day(), run.answer),
+        Err(e) => {
+            eprint!("{}", e.render(&input.name));
+            process::exit(1)
+        }
+    }
+}
+
+/*
+median timings of the given days (all by default) on their real inputs
+ */
+fn bench(days: &[String]) {
+    let solvers = if days.is_empty() { aoc::SOLVERS.to_vec() } else { days.iter().map(|day| parse_day(day)).collect() };
+    let timings = solvers.into_iter().map(|solver| {
+        let input = aoc::real_input(solver).unwrap_or_else(|e| fail(&e));
+        aoc::timing::measure(solver, &input.text, SAMPLES).unwrap_or_else(|e| {
+            eprint!("{}", e.render(&input.name));
+            process::exit(1)
+        })
+    }).collect::<Vec<_>>();
+    print!("{}", aoc::timing::table(&timings));
+}
+
+/*
+make sure the inputs of the given days are in the input cache, printing where they are
+ */
+fn fetch(days: &[String]) {
+    let cache = InputCache::from_env().unwrap_or_else(|e| fail(&e));
+    for day in days {
+        let day = parse_day(day).day();
+        cache.load(day).unwrap_or_else(|e| fail(&e));
+        println!("day{day}: {}", cache.path(day).display());
+    }
+}
+
+/*
+post an answer, by default the one computed from the day's real input, and say what the site made of it
+ */
+fn submit(day: &str, part: &str, answer: Option<&String>) {
+    let solver = parse_day(day);
+    let part = parse_part(part);
+    let answer = match answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let input = aoc::real_input(solver).unwrap_or_else(|e| fail(&e));
+            solver.solve(&input.text, part).unwrap_or_else(|e| {
+                eprint!("{}", e.render(&input.name));
+                process::exit(1)
+            })
+        }
+    };
+    let submitter = Submitter::from_env().unwrap_or_else(|e| fail(&e));
+    let response = submitter.submit(solver.day(), part, &answer, &aoc::answers_path(solver.day())).unwrap_or_else(|e| fail(&e));
+    let wait = response.wait.map(|wait| format!(", wait {}s before the next answer", wait.as_secs())).unwrap_or_default();
+    println!("day{} part{part}: {answer} is {}{wait}", solver.day(), match response.verdict {
+        Verdict::Correct => "right",
+        Verdict::TooHigh => "too high",
+        Verdict::TooLow => "too low",
+        Verdict::Wrong => "wrong",
+        Verdict::TooSoon => "not checked, submitted too soon",
+        Verdict::WrongLevel => "not checked, the part is solved or locked",
+    });
+    if response.verdict != Verdict::Correct {
+        process::exit(1)
+    }
+}
+
+/*
+create the dayN crate from the template, with its input from the input cache when it can be had
+ */
+fn new_day(day: &str) {
+    let day = day.parse().ok().filter(|day| (1..=25).contains(day)).unwrap_or_else(|| fail(USAGE));
+    let input = InputCache::from_env().and_then(|cache| cache.load(day));
+    if let Err(e) = &input {
+        eprintln!("src/input.txt left empty: {e}");
+    }
+    let files = aoc::scaffold::generate(&aoc::day_dir(day), day, input.ok().as_ref().map(|input| input.text.as_str()))
+        .unwrap_or_else(|e| fail(&e));
+    files.iter().for_each(|path| println!("created {}", path.display()));
+    println!("add day{day} to aoc's dependencies and SOLVERS to run it from here");
+}
+
+/*
+re-run the day's tests and real input whenever its sources or input change
+ */
+fn watch(day: &str) {
+    let day = day.parse().unwrap_or_else(|_| fail(USAGE));
+    aoc::watch::watch(day, POLL_INTERVAL).unwrap_or_else(|e| fail(&e));
+}
+
+fn parse_part(part: &str) -> Part {
+    part.parse().ok().and_then(Part::from_number).unwrap_or_else(|| fail(USAGE))
+}
+
+fn parse_day(day: &str) -> &'static dyn Solver {
+    let day: u8 = day.parse().unwrap_or_else(|_| fail(USAGE));
+    aoc::solver(day).unwrap_or_else(|| fail(&format!("no solution for day {day}")))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(2)
+}
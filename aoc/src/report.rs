@@ -0,0 +1,53 @@
+use serde::Serialize;
+use common::{Part, Run};
+
+/*
+machine readable result of one run, times in nanoseconds
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub answer_type: String,
+    pub parse_time_ns: u64,
+    pub solve_time_ns: u64,
+}
+
+impl Report {
+    pub fn new(day: u8, part: Part, run: Run) -> Report {
+        Report {
+            day,
+            part: match part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            answer: run.answer,
+            answer_type: run.answer_type.to_string(),
+            parse_time_ns: run.parse_time.as_nanos() as u64,
+            solve_time_ns: run.solve_time.as_nanos() as u64,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a report always serializes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let run = Run {
+            answer: "288".to_string(),
+            answer_type: "u64",
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_nanos(250),
+        };
+        assert_eq!(Report::new(6, Part::One, run).to_json(),
+                   r#"{"day":6,"part":1,"answer":"288","answer_type":"u64","parse_time_ns":3000,"solve_time_ns":250}"#);
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// (path in the new crate, template), {{N}} stands for the day number
+const TEMPLATES: [(&str, &str); 5] = [
+    ("Cargo.toml", include_str!("../templates/day/Cargo.toml.tmpl")),
+    ("src/main.rs", include_str!("../templates/day/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.tmpl")),
+    ("src/parsing.rs", include_str!("../templates/day/parsing.rs.tmpl")),
+    ("benches/bench_day{{N}}.rs", include_str!("../templates/day/bench.rs.tmpl")),
+];
+
+/*
+a new dayN crate in dir with the usual layout: binary, Solution with a parsing module, an example test
+that fails until it is filled in and a criterion bench; input is written to src/input.txt (empty without one).
+returns the files created
+ */
+pub fn generate(dir: &Path, day: u8, input: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let fill = |template: &str| template.replace("{{N}}", &day.to_string());
+    let files = TEMPLATES.iter()
+        .map(|(path, template)| (dir.join(fill(path)), fill(template)))
+        .chain([(dir.join("src/input.txt"), input.unwrap_or_default().to_string())])
+        .collect::<Vec<_>>();
+    for (path, content) in &files {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, content))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id())).join("day13");
+        let files = generate(&dir, 13, Some("1 2 3\n")).unwrap();
+        let names = files.iter().map(|path| path.strip_prefix(&dir).unwrap().to_string_lossy().into_owned()).collect::<Vec<_>>();
+        assert_eq!(names, ["Cargo.toml", "src/main.rs", "src/lib.rs", "src/parsing.rs", "benches/bench_day13.rs", "src/input.txt"]);
+
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"day13\""));
+        assert!(manifest.contains("[[bench]]\nname = \"bench_day13\"\nharness = false"));
+        ["nom", "itertools", "criterion"].iter().for_each(|dep| assert!(manifest.contains(&format!("{dep} = {{ workspace = true }}")), "{dep}"));
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day13 {\n    const DAY: u8 = 13;"));
+        assert!(!lib.contains("todo!"), "the solvers must not panic before they are written");
+        assert_eq!(fs::read_to_string(dir.join("src/input.txt")).unwrap(), "1 2 3\n");
+        assert!(files.iter().all(|path| !fs::read_to_string(path).unwrap().contains("{{")));
+
+        assert_eq!(generate(&dir, 13, None), Err(format!("{} already exists", dir.display())));
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use common::answers::{Fact, Relation};
+use common::Part;
+use crate::inputs::{self, InputCache, SESSION_VAR};
+
+/*
+what the site made of a submitted answer
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // submitted before the previous wait was over, the answer was not checked
+    TooSoon,
+    // the part is already solved or not unlocked yet
+    WrongLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    // how long until the next answer may be submitted
+    pub wait: Option<Duration>,
+}
+
+impl Response {
+    pub fn parse(body: &str) -> Result<Response, String> {
+        let verdict = if body.contains("That's the right answer") {
+            Verdict::Correct
+        } else if body.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if body.contains("That's not the right answer") {
+            if body.contains("too high") {
+                Verdict::TooHigh
+            } else if body.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return Err("unrecognised response to the submission".to_string());
+        };
+        Ok(Response { verdict, wait: parse_wait(body) })
+    }
+}
+
+/*
+"You have 1m 5s left to wait" after submitting too soon, "Please wait one minute" or "wait 5 minutes" after a wrong answer
+ */
+fn parse_wait(body: &str) -> Option<Duration> {
+    if let Some(idx) = body.find(" left to wait") {
+        let before = &body[..idx];
+        let left = &before[before.rfind("You have ")? + "You have ".len()..];
+        return left.split_whitespace()
+            .map(|amount| match (amount.strip_suffix('m'), amount.strip_suffix('s')) {
+                (Some(minutes), _) => minutes.parse::<u64>().ok().map(|minutes| minutes * 60),
+                (_, Some(seconds)) => seconds.parse::<u64>().ok(),
+                _ => None,
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+    let idx = body.find("lease wait ")?;
+    let mut words = body[idx + "lease wait ".len()..].split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+    words.next()?.starts_with("minute").then(|| Duration::from_secs(minutes * 60))
+}
+
+impl Verdict {
+    /*
+    what the verdict tells about the answer, in the form kept in answers.txt
+     */
+    pub fn fact(&self, part: Part, answer: &str) -> Option<Fact> {
+        let relation = match self {
+            Verdict::Correct => Relation::Is,
+            // the answer was above the right one, so the right one is below it
+            Verdict::TooHigh => Relation::Below,
+            Verdict::TooLow => Relation::Above,
+            Verdict::Wrong => Relation::IsNot,
+            Verdict::TooSoon | Verdict::WrongLevel => return None,
+        };
+        Some(Fact { part, relation, value: answer.to_string() })
+    }
+}
+
+/*
+posts answers to base_url/day/N/answer, keeping each day's verdicts as facts in its answers file and
+the time until the site takes another answer in dir/dayN.wait
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submitter {
+    pub dir: PathBuf,
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Submitter {
+    /*
+    configured like the input cache, see InputCache::from_env
+     */
+    pub fn from_env() -> Result<Submitter, String> {
+        let InputCache { dir, base_url, session } = InputCache::from_env()?;
+        Ok(Submitter { dir, base_url, session })
+    }
+
+    /*
+    submit unless the answers file already rules the answer out, the part is already solved or the last
+    response asked to wait; the verdict is appended to the answers file
+     */
+    pub fn submit(&self, day: u8, part: Part, answer: &str, answers_file: &Path) -> Result<Response, String> {
+        let answers = crate::read_answers(answers_file)?;
+        if let Some(expected) = answers.expected(part) {
+            return Err(format!("day{day} part{part} is already solved, the answer is {expected}"));
+        }
+        if let Some(fact) = answers.contradiction(part, answer) {
+            return Err(format!("not submitting {answer} for day{day}: {fact}"));
+        }
+        if let Some(wait) = self.wait_left(day) {
+            return Err(format!("not submitting for day{day} yet, {}s left to wait", wait.as_secs().max(1)));
+        }
+
+        let session = self.session.as_deref().ok_or_else(|| format!("{SESSION_VAR} is not set"))?;
+        let url = format!("{}/day/{day}/answer", self.base_url.trim_end_matches('/'));
+        let body = inputs::agent().post(&url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| inputs::describe_error(&url, day, e))?
+            .into_string()
+            .map_err(|e| format!("{url}: {e}"))?;
+        let response = Response::parse(&body).map_err(|e| format!("{url}: {e}"))?;
+
+        if let Some(wait) = response.wait {
+            self.wait_until(day, SystemTime::now() + wait)?;
+        }
+        if let Some(fact) = response.verdict.fact(part, answer) {
+            record(answers_file, &fact)?;
+        }
+        Ok(response)
+    }
+
+    fn wait_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}.wait"))
+    }
+
+    fn wait_left(&self, day: u8) -> Option<Duration> {
+        let until = fs::read_to_string(self.wait_path(day)).ok()?.trim().parse().ok()?;
+        (UNIX_EPOCH + Duration::from_secs(until)).duration_since(SystemTime::now()).ok()
+    }
+
+    fn wait_until(&self, day: u8, until: SystemTime) -> Result<(), String> {
+        let path = self.wait_path(day);
+        let seconds = until.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, format!("{seconds}\n")))
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+}
+
+fn record(answers_file: &Path, fact: &Fact) -> Result<(), String> {
+    let existing = fs::read_to_string(answers_file).unwrap_or_default();
+    let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+    fs::OpenOptions::new().create(true).append(true).open(answers_file)
+        .and_then(|mut file| writeln!(file, "{separator}{fact}"))
+        .map_err(|e| format!("cannot write {}: {e}", answers_file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use super::*;
+
+    const RIGHT: &str = "<article><p>That's the right answer!  You are one gold star closer to restoring snow operations.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [<a href=\"/2023/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href=\"/2023/day/1\">Return to Day 1</a>]</p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>";
+
+    #[test]
+    fn test_parse_response() {
+        let response = |verdict, wait: Option<u64>| Ok(Response { verdict, wait: wait.map(Duration::from_secs) });
+        assert_eq!(Response::parse(RIGHT), response(Verdict::Correct, None));
+        assert_eq!(Response::parse(TOO_HIGH), response(Verdict::TooHigh, Some(60)));
+        assert_eq!(Response::parse(TOO_LOW), response(Verdict::TooLow, Some(300)));
+        assert_eq!(Response::parse(WRONG), response(Verdict::Wrong, None));
+        assert_eq!(Response::parse(TOO_SOON), response(Verdict::TooSoon, Some(65)));
+        assert_eq!(Response::parse(WRONG_LEVEL), response(Verdict::WrongLevel, None));
+        assert!(Response::parse("<html>Internal error</html>").is_err());
+    }
+
+    /*
+    answers every submission for day 1 by its value: 53268 is right, above is too high, below too low
+    and 1 comes too soon; records the form of every request
+     */
+    fn mock_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2023", server.server_addr().to_ip().unwrap());
+        let forms = Arc::new(Mutex::new(vec![]));
+        let seen = forms.clone();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut form = String::new();
+                request.as_reader().read_to_string(&mut form).unwrap();
+                seen.lock().unwrap().push(form.clone());
+                let answer = form.split('&').find_map(|field| field.strip_prefix("answer=")).unwrap_or_default();
+                let body = match answer.parse::<u64>() {
+                    _ if request.url() != "/2023/day/1/answer" => WRONG_LEVEL,
+                    Ok(1) => TOO_SOON,
+                    Ok(53268) => RIGHT,
+                    Ok(answer) if answer > 53268 => TOO_HIGH,
+                    Ok(_) => TOO_LOW,
+                    Err(_) => WRONG,
+                };
+                let _ = request.respond(tiny_http::Response::from_string(body));
+            }
+        });
+        (base_url, forms)
+    }
+
+    fn temp_submitter(name: &str, base_url: &str) -> (Submitter, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let answers_file = dir.join("answers.txt");
+        (Submitter { dir, base_url: base_url.to_string(), session: Some("53616c7465".to_string()) }, answers_file)
+    }
+
+    #[test]
+    fn test_history_and_bounds() {
+        let (base_url, forms) = mock_server();
+        let (submitter, answers_file) = temp_submitter("history", &base_url);
+        // the computed part2 neither counts as solved nor keeps other answers out
+        fs::write(&answers_file, "# day1\npart1 = 53080\npart2 ?= 53268").unwrap();
+
+        assert_eq!(submitter.submit(1, Part::One, "1", &answers_file), Err("day1 part1 is already solved, the answer is 53080".to_string()));
+        assert_eq!(submitter.submit(1, Part::Two, "53248x", &answers_file).unwrap().verdict, Verdict::Wrong);
+        assert_eq!(submitter.submit(1, Part::Two, "53248x", &answers_file), Err("not submitting 53248x for day1: part2 != 53248x".to_string()));
+        assert_eq!(submitter.submit(1, Part::Two, "60000", &answers_file).unwrap(), Response { verdict: Verdict::TooHigh, wait: Some(Duration::from_secs(60)) });
+        assert_eq!(fs::read_to_string(&answers_file).unwrap(), "# day1\npart1 = 53080\npart2 ?= 53268\npart2 != 53248x\npart2 < 60000\n");
+        assert_eq!(*forms.lock().unwrap(), vec!["level=2&answer=53248x", "level=2&answer=60000"]);
+
+        // out of bounds answers are refused even once the wait is over
+        fs::remove_file(submitter.wait_path(1)).unwrap();
+        assert_eq!(submitter.submit(1, Part::Two, "70000", &answers_file), Err("not submitting 70000 for day1: part2 < 60000".to_string()));
+        assert_eq!(submitter.submit(1, Part::Two, "53268", &answers_file).unwrap().verdict, Verdict::Correct);
+        assert!(submitter.submit(1, Part::Two, "53268", &answers_file).unwrap_err().contains("already solved"));
+        assert_eq!(crate::read_answers(&answers_file).unwrap().expected(Part::Two), Some("53268"));
+        assert_eq!(forms.lock().unwrap().len(), 3);
+        fs::remove_dir_all(&submitter.dir).unwrap();
+    }
+
+    #[test]
+    fn test_wait() {
+        let (base_url, forms) = mock_server();
+        let (submitter, answers_file) = temp_submitter("wait", &base_url);
+
+        assert_eq!(submitter.submit(1, Part::One, "1", &answers_file).unwrap(), Response { verdict: Verdict::TooSoon, wait: Some(Duration::from_secs(65)) });
+        assert!(!answers_file.exists());
+        let refused = submitter.submit(1, Part::One, "2", &answers_file).unwrap_err();
+        assert!(refused.starts_with("not submitting for day1 yet, "), "{refused}");
+        assert_eq!(forms.lock().unwrap().len(), 1);
+
+        // other days are not held up
+        assert_eq!(submitter.submit(2, Part::One, "2", &answers_file).unwrap().verdict, Verdict::WrongLevel);
+        fs::remove_dir_all(&submitter.dir).unwrap();
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+use common::{Part, SolveError, Solver};
+
+/*
+median times of one day over a number of runs
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayTimings {
+    pub day: u8,
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+pub fn measure(solver: &dyn Solver, input: &str, samples: usize) -> Result<DayTimings, SolveError> {
+    let mut parse = vec![];
+    let mut part1 = vec![];
+    let mut part2 = vec![];
+    for _ in 0..samples.max(1) {
+        let run = solver.run(input, Part::One)?;
+        parse.push(run.parse_time);
+        part1.push(run.solve_time);
+        let run = solver.run(input, Part::Two)?;
+        parse.push(run.parse_time);
+        part2.push(run.solve_time);
+    }
+    Ok(DayTimings { day: solver.day(), parse: median(&mut parse), part1: median(&mut part1), part2: median(&mut part2) })
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+/*
+one row per day plus a total, times as median
+ */
+pub fn table(timings: &[DayTimings]) -> String {
+    let mut table = format!("{:>5} {:>12} {:>12} {:>12} {:>12}\n", "day", "parse", "part1", "part2", "total");
+    let row = |label: &str, parse: Duration, part1: Duration, part2: Duration| {
+        format!("{:>5} {:>12} {:>12} {:>12} {:>12}\n",
+                label, format!("{:.1?}", parse), format!("{:.1?}", part1), format!("{:.1?}", part2), format!("{:.1?}", parse + part1 + part2))
+    };
+    for t in timings {
+        table += &row(&t.day.to_string(), t.parse, t.part1, t.part2);
+    }
+    table += &row("all",
+                  timings.iter().map(|t| t.parse).sum(),
+                  timings.iter().map(|t| t.part1).sum(),
+                  timings.iter().map(|t| t.part2).sum());
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&mut [ms(5), ms(1), ms(3)]), ms(3));
+        assert_eq!(median(&mut [ms(7)]), ms(7));
+    }
+
+    #[test]
+    fn test_measure() {
+        let day6 = crate::solver(6).unwrap();
+        let timings = measure(day6, "Time:      7  15   30\nDistance:  9  40  200\n", 3).unwrap();
+        assert_eq!(timings.day, 6);
+        assert!(measure(day6, "Time: x", 3).is_err());
+    }
+
+    #[test]
+    fn test_table() {
+        let ms = Duration::from_millis;
+        let table = table(&[
+            DayTimings { day: 1, parse: ms(1), part1: ms(2), part2: ms(3) },
+            DayTimings { day: 2, parse: ms(1), part1: ms(1), part2: ms(1) },
+        ]);
+        assert_eq!(table.lines().collect::<Vec<_>>(), [
+            "  day        parse        part1        part2        total",
+            "    1        1.0ms        2.0ms        3.0ms        6.0ms",
+            "    2        1.0ms        1.0ms        1.0ms        3.0ms",
+            "  all        2.0ms        3.0ms        4.0ms        9.0ms",
+        ]);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, SystemTime};
+use common::answers::Answers;
+use common::Part;
+use crate::inputs::InputCache;
+
+// lines of a failing test run shown, counted from the end
+const FAILURE_TAIL: usize = 40;
+
+/*
+modification time and size of every file under some paths, compared between polls to spot edits
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    /*
+    paths may be files or directories, which are walked; missing ones are left out and show up once created
+     */
+    pub fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut files = BTreeMap::new();
+        let mut pending = paths.to_vec();
+        while let Some(path) = pending.pop() {
+            let Ok(metadata) = fs::metadata(&path) else { continue };
+            if metadata.is_dir() {
+                pending.extend(fs::read_dir(&path).into_iter().flatten().flatten().map(|entry| entry.path()));
+            } else {
+                files.insert(path, (metadata.modified().ok(), metadata.len()));
+            }
+        }
+        Snapshot(files)
+    }
+
+    /*
+    files created, deleted or modified since previous
+     */
+    pub fn changed(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let modified = self.0.iter().filter(|(path, stamp)| previous.0.get(*path) != Some(stamp));
+        let deleted = previous.0.iter().filter(|(path, _)| !self.0.contains_key(*path));
+        let mut changed = modified.chain(deleted).map(|(path, _)| path.clone()).collect::<Vec<_>>();
+        changed.sort();
+        changed
+    }
+}
+
+/*
+the answers a day binary prints, one "partN: answer" per line
+ */
+pub fn parse_answers(output: &str) -> Vec<(Part, String)> {
+    output.lines()
+        .filter_map(|line| line.strip_prefix("part")?.split_once(": "))
+        .filter_map(|(part, answer)| Some((Part::from_number(part.parse().ok()?)?, answer.trim().to_string())))
+        .collect()
+}
+
+/*
+one line per part: its answer, how it differs from the previous run and whether answers.txt rules it out
+ */
+pub fn compare(previous: Option<&[(Part, String)]>, current: &[(Part, String)], known: &Answers) -> Vec<String> {
+    [Part::One, Part::Two].into_iter().filter_map(|part| {
+        let find = |answers: &[(Part, String)]| answers.iter().find(|(p, _)| *p == part).map(|(_, answer)| answer.clone());
+        let before = previous.and_then(find);
+        let now = find(current);
+        let line = match (&before, &now) {
+            (None, None) => return None,
+            (_, Some(now)) if previous.is_none() => format!("part{part}: {now}"),
+            (Some(before), Some(now)) if before == now => format!("part{part}: {now} (unchanged)"),
+            (before, now) => format!("part{part}: {} -> {}",
+                before.as_deref().unwrap_or("(none)"), now.as_deref().unwrap_or("(none)")),
+        };
+        Some(match now.as_deref().and_then(|now| known.contradiction(part, now)) {
+            Some(fact) => format!("{line}, but answers.txt has {fact}"),
+            None => line,
+        })
+    }).collect()
+}
+
+/*
+poll the day's crate and its cached input, re-running its tests and its real input through `cargo run` on every
+change. runs until interrupted; the day only needs to be a crate in the workspace, not one of SOLVERS
+ */
+pub fn watch(day: u8, interval: Duration) -> Result<(), String> {
+    let dir = crate::day_dir(day);
+    if !dir.join("Cargo.toml").exists() {
+        return Err(format!("no day{day} crate in {}", dir.display()));
+    }
+    let mut paths = vec![dir.join("src"), dir.join("Cargo.toml"), crate::answers_path(day)];
+    paths.extend(InputCache::from_env().ok().map(|cache| cache.path(day)));
+
+    let mut snapshot = Snapshot::default();
+    let mut previous = None;
+    loop {
+        let current = Snapshot::take(&paths);
+        let changed = current.changed(&snapshot);
+        if !changed.is_empty() {
+            if previous.is_some() {
+                changed.iter().for_each(|path| println!("changed: {}", path.strip_prefix(&dir).unwrap_or(path).display()));
+            }
+            snapshot = current;
+            previous = Some(round(day, &dir, previous.as_deref()));
+            println!("watching day{day}, ctrl-c to stop");
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+// tests then the real input; returns the answers to compare the next round with
+fn round(day: u8, dir: &Path, previous: Option<&[(Part, String)]>) -> Vec<(Part, String)> {
+    let package = format!("day{day}");
+    match cargo(dir, &["test", "-q", "-p", &package], None) {
+        Ok(output) if output.status.success() => println!("examples: ok"),
+        Ok(output) => {
+            println!("examples: FAILED");
+            print!("{}", tail(&output, FAILURE_TAIL));
+        }
+        Err(e) => println!("examples: {e}"),
+    }
+    let input = match crate::day_input(day, None) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("real input: {e}");
+            return previous.map(<[_]>::to_vec).unwrap_or_default();
+        }
+    };
+    let answers = match cargo(dir, &["run", "-q", "-p", &package, "--", "-"], Some(&input.text)) {
+        Ok(output) if output.status.success() => parse_answers(&String::from_utf8_lossy(&output.stdout)),
+        Ok(output) => {
+            println!("real input: FAILED");
+            print!("{}", tail(&output, FAILURE_TAIL));
+            vec![]
+        }
+        Err(e) => {
+            println!("real input: {e}");
+            vec![]
+        }
+    };
+    let known = crate::known_answers(day).unwrap_or_else(|e| {
+        println!("{e}");
+        Answers::default()
+    });
+    compare(previous, &answers, &known).iter().for_each(|line| println!("{line}"));
+    answers
+}
+
+// cargo from the workspace root, with stdin fed from input when given
+fn cargo(dir: &Path, args: &[&str], input: Option<&str>) -> Result<Output, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut child = Command::new(&cargo)
+        .args(args)
+        .current_dir(dir.join(".."))
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run {cargo}: {e}"))?;
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        // a binary that fails before reading closes the pipe early, its output says why
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    child.wait_with_output().map_err(|e| format!("cannot run {cargo}: {e}"))
+}
+
+fn tail(output: &Output, lines: usize) -> String {
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let all = text.lines().collect::<Vec<_>>();
+    all[all.len().saturating_sub(lines)..].iter().map(|line| format!("{line}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "fn a() {}").unwrap();
+        let paths = [dir.join("src"), dir.join("input.txt")];
+
+        let before = Snapshot::take(&paths);
+        assert_eq!(before.changed(&Snapshot::default()), [dir.join("src/lib.rs")]);
+        assert!(Snapshot::take(&paths).changed(&before).is_empty());
+
+        fs::write(dir.join("src/lib.rs"), "fn ab() {}").unwrap();
+        fs::write(dir.join("input.txt"), "1\n").unwrap();
+        let after = Snapshot::take(&paths);
+        assert_eq!(after.changed(&before), [dir.join("input.txt"), dir.join("src/lib.rs")]);
+
+        fs::remove_file(dir.join("input.txt")).unwrap();
+        assert_eq!(Snapshot::take(&paths).changed(&after), [dir.join("input.txt")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(parse_answers("part1: 142\npart2: 281\n"), [(Part::One, "142".to_string()), (Part::Two, "281".to_string())]);
+        assert_eq!(parse_answers("warming up\npart3: 1\npart2: [1, 2]\n"), [(Part::Two, "[1, 2]".to_string())]);
+    }
+
+    #[test]
+    fn test_compare() {
+        let answers = |one: &str, two: &str| vec![(Part::One, one.to_string()), (Part::Two, two.to_string())];
+        let known = Answers::parse("part1 < 100\n").unwrap();
+        assert_eq!(compare(None, &answers("42", "7"), &known), ["part1: 42", "part2: 7"]);
+        assert_eq!(compare(Some(&answers("42", "7")), &answers("142", "7"), &known),
+                   ["part1: 42 -> 142, but answers.txt has part1 < 100", "part2: 7 (unchanged)"]);
+        assert_eq!(compare(Some(&answers("42", "7")), &[], &Answers::default()), ["part1: 42 -> (none)", "part2: 7 -> (none)"]);
+        assert!(compare(Some(&[]), &[], &known).is_empty());
+    }
+}
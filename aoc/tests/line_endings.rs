@@ -0,0 +1,189 @@
+/*
+solves every day's examples saved with LF, CRLF, trailing spaces and trailing blank lines, which must all agree
+ */
+use common::input::line_ending_variants;
+use common::Part;
+
+fn check(day: u8, part: Part, example: &str, expected: &str) {
+    let solver = aoc::solver(day).unwrap();
+    for (variant, input) in line_ending_variants(example) {
+        match solver.solve(&input, part) {
+            Ok(answer) => assert_eq!(answer, expected, "day{day} part{part} with {variant}"),
+            Err(e) => panic!("day{day} part{part} with {variant}:\n{}", e.render("example")),
+        }
+    }
+}
+
+macro_rules! line_endings {
+    ($($name:ident: $day:expr, $part:expr, $example:expr => $expected:expr;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day, $part, $example, $expected);
+            }
+        )*
+    };
+}
+
+const DAY1_PART1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+const DAY1_PART2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+const DAY2: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+const DAY3: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+const DAY4: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+const DAY5: &str = include_str!("../../day5/src/testcase1.txt");
+
+const DAY6: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+const DAY7: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+const DAY8_PART1: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+// the puzzle's part2 example, with the AAA and ZZZ every real input has for part1. that ghost is on a Z node from
+// its first step on, so the answer stays 6
+const DAY8_PART2: &str = "\
+LR
+
+AAA = (ZZZ, ZZZ)
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+ZZZ = (ZZZ, ZZZ)
+";
+
+const DAY9: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+const DAY10_PART1: &str = "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+";
+
+const DAY10_PART2: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+const DAY11: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+const DAY12: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+line_endings! {
+    day1_part1: 1, Part::One, DAY1_PART1 => "142";
+    day1_part2: 1, Part::Two, DAY1_PART2 => "281";
+    day2_part1: 2, Part::One, DAY2 => "8";
+    day2_part2: 2, Part::Two, DAY2 => "2286";
+    day3_part1: 3, Part::One, DAY3 => "4361";
+    day3_part2: 3, Part::Two, DAY3 => "467835";
+    day4_part1: 4, Part::One, DAY4 => "13";
+    day4_part2: 4, Part::Two, DAY4 => "30";
+    day5_part1: 5, Part::One, DAY5 => "35";
+    day5_part2: 5, Part::Two, DAY5 => "46";
+    day6_part1: 6, Part::One, DAY6 => "288";
+    day6_part2: 6, Part::Two, DAY6 => "71503";
+    day7_part1: 7, Part::One, DAY7 => "6440";
+    day7_part2: 7, Part::Two, DAY7 => "5905";
+    day8_part1: 8, Part::One, DAY8_PART1 => "6";
+    day8_part2: 8, Part::Two, DAY8_PART2 => "6";
+    day9_part1: 9, Part::One, DAY9 => "114";
+    day9_part2: 9, Part::Two, DAY9 => "2";
+    day10_part1: 10, Part::One, DAY10_PART1 => "8";
+    day10_part2: 10, Part::Two, DAY10_PART2 => "10";
+    day11_part1: 11, Part::One, DAY11 => "374";
+    day11_part2: 11, Part::Two, DAY11 => "82000210";
+    day12_part1: 12, Part::One, DAY12 => "21";
+    day12_part2: 12, Part::Two, DAY12 => "525152";
+}
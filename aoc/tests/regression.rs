@@ -0,0 +1,54 @@
+/*
+solves every day's real input and checks it against the facts in dayN/answers.txt
+ */
+use common::Part;
+
+fn check(day: u8, part: Part) {
+    let answers = aoc::known_answers(day).unwrap_or_else(|e| panic!("{}", e));
+    assert!(answers.expected(part).or(answers.computed(part)).is_some(), "day{day}/answers.txt has no answer for part{part}");
+    let solver = aoc::solver(day).unwrap();
+    let input = aoc::real_input(solver).unwrap_or_else(|e| panic!("{}", e));
+    let answer = solver.solve(&input.text, part).unwrap_or_else(|e| panic!("{}", e.render(&input.name)));
+    if let Some(fact) = answers.regression(part, &answer) {
+        panic!("day{day} part{part} answered {answer}, but {fact}");
+    }
+}
+
+macro_rules! regression {
+    ($($(#[$attr:meta])* $name:ident: $day:expr, $part:expr;)*) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $name() {
+                check($day, $part);
+            }
+        )*
+    };
+}
+
+regression! {
+    day1_part1: 1, Part::One;
+    day1_part2: 1, Part::Two;
+    day2_part1: 2, Part::One;
+    day2_part2: 2, Part::Two;
+    day3_part1: 3, Part::One;
+    day3_part2: 3, Part::Two;
+    day4_part1: 4, Part::One;
+    day4_part2: 4, Part::Two;
+    day5_part1: 5, Part::One;
+    day5_part2: 5, Part::Two;
+    day6_part1: 6, Part::One;
+    day6_part2: 6, Part::Two;
+    day7_part1: 7, Part::One;
+    day7_part2: 7, Part::Two;
+    day8_part1: 8, Part::One;
+    day8_part2: 8, Part::Two;
+    day9_part1: 9, Part::One;
+    day9_part2: 9, Part::Two;
+    day10_part1: 10, Part::One;
+    day10_part2: 10, Part::Two;
+    day11_part1: 11, Part::One;
+    day11_part2: 11, Part::Two;
+    day12_part1: 12, Part::One;
+    day12_part2: 12, Part::Two;
+}
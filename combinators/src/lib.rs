@@ -0,0 +1,169 @@
+/*
+nom building blocks for the shapes puzzle inputs keep coming in: number lists, "Label N:" prefixes,
+blocks separated by blank lines and X = (A, B) pairs. text works on &str and bytes on &[u8], both fail with
+common's Expected so errors read the same whichever one a day parses with
+ */
+
+macro_rules! shapes {
+    ($input:ty) => {
+        use nom::character::complete::{line_ending, space0, space1};
+        use nom::combinator::{cut, value};
+        use nom::multi::separated_list1;
+        use nom::sequence::{delimited, pair, separated_pair};
+        use nom::Parser;
+        use common::parse::{Expected, IResult};
+
+        /*
+        one or more values separated by spaces, like 41 48  6 31
+         */
+        pub fn numbers<'a, O>(number: impl Parser<&'a $input, O, Expected<&'a $input>>) -> impl FnMut(&'a $input) -> IResult<&'a $input, Vec<O>> {
+            separated_list1(space1, number)
+        }
+
+        /*
+        a "Label N:" prefix and the spaces after it, giving N
+         */
+        pub fn label_id<'a, O>(label: &'static str, id: impl Parser<&'a $input, O, Expected<&'a $input>>) -> impl FnMut(&'a $input) -> IResult<&'a $input, O> {
+            delimited(pair(tag(label), space1), id, pair(tag(":"), space0))
+        }
+
+        pub fn blank_line(input: &$input) -> IResult<&$input, ()> {
+            value((), pair(line_ending, line_ending))(input)
+        }
+
+        /*
+        one or more consecutive lines, each read by line
+         */
+        pub fn lines_of<'a, O>(line: impl Parser<&'a $input, O, Expected<&'a $input>>) -> impl FnMut(&'a $input) -> IResult<&'a $input, Vec<O>> {
+            separated_list1(line_ending, line)
+        }
+
+        /*
+        one or more blocks of lines with a blank line between them
+         */
+        pub fn blocks<'a, O>(block: impl Parser<&'a $input, O, Expected<&'a $input>>) -> impl FnMut(&'a $input) -> IResult<&'a $input, Vec<O>> {
+            separated_list1(blank_line, block)
+        }
+
+        /*
+        X = (A, B) as (X, (A, B)), committed to once X is followed by " = "
+         */
+        pub fn labelled_pair<'a, L, O, P>(label: impl Parser<&'a $input, L, Expected<&'a $input>>, item: P) -> impl FnMut(&'a $input) -> IResult<&'a $input, (L, (O, O))>
+            where P: Parser<&'a $input, O, Expected<&'a $input>> + Clone {
+            separated_pair(
+                label, cut(tag(" = ")),
+                cut(delimited(tag("("), separated_pair(item.clone(), tag(", "), item), tag(")"))),
+            )
+        }
+    };
+}
+
+pub mod text {
+    pub use common::parse::{complete, lines, tag};
+
+    shapes!(str);
+}
+
+pub mod bytes {
+    use common::ParseError;
+
+    shapes!([u8]);
+
+    /*
+    nom's tag, reporting the literal itself as what was expected
+     */
+    pub fn tag<'a>(literal: &'static str) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8]> {
+        move |input: &'a [u8]| nom::bytes::complete::tag(literal)(input)
+            .map_err(|e| e.map(|_: Expected<&[u8]>| Expected::new(input, format!("{:?}", literal))))
+    }
+
+    /*
+    run parser over the whole source, allowing only trailing whitespace after it
+     */
+    pub fn complete<'a, O>(mut parser: impl Parser<&'a [u8], O, Expected<&'a [u8]>>, source: &'a [u8]) -> Result<O, ParseError> {
+        match parser.parse(source) {
+            Ok((remaining, output)) if remaining.trim_ascii().is_empty() => Ok(output),
+            Ok((remaining, _)) => Err(parse_error(source, remaining.trim_ascii_start(), "end of input")),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(parse_error(source, e.input, e.describe())),
+            Err(nom::Err::Incomplete(_)) => Err(parse_error(source, &source[source.len()..], "more input")),
+        }
+    }
+
+    /*
+    parse every line on its own, reporting errors with their line number in source
+     */
+    pub fn lines<O>(source: &[u8], parse_line: impl Fn(&[u8]) -> Result<O, ParseError>) -> Result<Vec<O>, ParseError> {
+        let source = source.strip_suffix(b"\n").unwrap_or(source);
+        if source.is_empty() {
+            return Ok(vec![]);
+        }
+        source.split(|&b| b == b'\n').enumerate()
+            .map(|(idx, line)| parse_line(line.strip_suffix(b"\r").unwrap_or(line)).map_err(|e| e.below(idx)))
+            .collect()
+    }
+
+    // position is the rest of source where parsing stopped, bytes that are not UTF-8 are shown as U+FFFD
+    fn parse_error(source: &[u8], position: &[u8], expected: impl Into<String>) -> ParseError {
+        let offset = source.len() - position.len();
+        let before = String::from_utf8_lossy(&source[..offset]);
+        let text = format!("{}{}", before, String::from_utf8_lossy(position));
+        ParseError::at_offset(&text, before.len(), expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::{alpha1, alphanumeric1, i32, i64};
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(text::numbers(i32)("41 48  6 -3 | 9"), Ok((" | 9", vec![41, 48, 6, -3])));
+        assert_eq!(bytes::numbers(i64)(b"41 48  6 -3 | 9"), Ok((&b" | 9"[..], vec![41, 48, 6, -3])));
+    }
+
+    #[test]
+    fn test_label_id() {
+        assert_eq!(text::label_id("Card", i32)("Card   1: 41 48"), Ok(("41 48", 1)));
+        assert_eq!(bytes::label_id("Game", i32)(b"Game 12: 3 blue"), Ok((&b"3 blue"[..], 12)));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "a:\n1 2\n3\n\r\nb:\n4\n";
+        let block = |input| nom::sequence::pair(alpha1, nom::sequence::preceded(text::tag(":\n"), text::lines_of(text::numbers(i32))))(input);
+        assert_eq!(text::complete(text::blocks(block), input), Ok(vec![("a", vec![vec![1, 2], vec![3]]), ("b", vec![vec![4]])]));
+    }
+
+    #[test]
+    fn test_labelled_pair() {
+        assert_eq!(text::labelled_pair(alphanumeric1, alphanumeric1)("AAA = (BBB, 11Z)"), Ok(("", ("AAA", ("BBB", "11Z")))));
+        assert_eq!(bytes::labelled_pair(alphanumeric1, alphanumeric1)(b"AAA = (BBB, 11Z)"), Ok((&b""[..], (&b"AAA"[..], (&b"BBB"[..], &b"11Z"[..])))));
+        assert!(matches!(text::labelled_pair(alphanumeric1, alphanumeric1)("AAA"), Err(nom::Err::Failure(_))));
+    }
+
+    fn game(line: &str) -> Result<(i32, i32), common::ParseError> {
+        text::complete(nom::sequence::pair(text::label_id("Game", i32), i32), line)
+    }
+
+    fn game_bytes(line: &[u8]) -> Result<(i32, i32), common::ParseError> {
+        bytes::complete(nom::sequence::pair(bytes::label_id("Game", i32), i32), line)
+    }
+
+    #[test]
+    fn test_errors_agree() {
+        let source = "Game 1: 3\nGame x: 4\n";
+        let e = text::lines(source, game).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str(), e.found.as_str()), (2, 6, "number", "\"x:\""));
+        assert_eq!(bytes::lines(source.as_bytes(), game_bytes), Err(e));
+        assert_eq!(bytes::lines(b"Game 1: 3\r\nGame 2: 4", game_bytes), Ok(vec![(1, 3), (2, 4)]));
+    }
+
+    #[test]
+    fn test_bytes_error_position() {
+        let e = bytes::complete(bytes::numbers(i32), b"1 2\xff 3").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 4, "end of input"));
+        let e = bytes::complete(bytes::label_id("Card", i32), b"\xffCard 1:").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (1, "\"Card\""));
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use crate::{ParseError, Part};
+
+/*
+what we know about the answers for one day's real input, one fact per line:
+
+    # comment
+    part1 = 8180
+    part1 != 8273
+    part1 < 8322
+    part2 > 100
+    part2 ?= 1234
+
+all but ?= come from the site's verdicts on submitted answers. ?= notes what the solver answered before anything
+was submitted, so a regression shows up without it passing for a verified answer
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    pub facts: Vec<Fact>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fact {
+    pub part: Part,
+    pub relation: Relation,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    Is,
+    IsNot,
+    Below,
+    Above,
+    // computed, not submitted
+    Computed,
+}
+
+const RELATIONS: [(&str, Relation); 5] = [
+    ("!=", Relation::IsNot),
+    ("?=", Relation::Computed),
+    ("=", Relation::Is),
+    ("<", Relation::Below),
+    (">", Relation::Above),
+];
+
+impl Answers {
+    pub fn parse(source: &str) -> Result<Answers, ParseError> {
+        let facts = source.lines().enumerate()
+            .map(|(idx, line)| (idx, line.trim_end()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(idx, line)| Fact::parse(line).map_err(|e| e.below(idx)))
+            .collect::<Result<_, _>>()?;
+        Ok(Answers { facts })
+    }
+
+    /*
+    the verified answer, if one has been recorded
+     */
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        self.facts.iter()
+            .find(|fact| fact.part == part && fact.relation == Relation::Is)
+            .map(|fact| fact.value.as_str())
+    }
+
+    /*
+    the answer the solver gave before anything was submitted, if one has been noted
+     */
+    pub fn computed(&self, part: Part) -> Option<&str> {
+        self.facts.iter()
+            .find(|fact| fact.part == part && fact.relation == Relation::Computed)
+            .map(|fact| fact.value.as_str())
+    }
+
+    /*
+    the first verdict of the site that answer contradicts
+     */
+    pub fn contradiction(&self, part: Part, answer: &str) -> Option<&Fact> {
+        self.facts.iter().find(|fact| fact.part == part && fact.relation != Relation::Computed && !fact.holds_for(answer))
+    }
+
+    /*
+    the first recorded fact that answer contradicts, computed answers included
+     */
+    pub fn regression(&self, part: Part, answer: &str) -> Option<&Fact> {
+        self.facts.iter().find(|fact| fact.part == part && !fact.holds_for(answer))
+    }
+}
+
+impl Fact {
+    fn parse(line: &str) -> Result<Fact, ParseError> {
+        let part = match line.split_whitespace().next() {
+            Some("part1") => Part::One,
+            Some("part2") => Part::Two,
+            _ => return Err(ParseError::at(line, line.trim_start(), "\"part1\" or \"part2\"")),
+        };
+        let rest = line.trim_start()["partN".len()..].trim_start();
+        let (symbol, relation) = RELATIONS.iter()
+            .find(|(symbol, _)| rest.starts_with(symbol))
+            .ok_or_else(|| ParseError::at(line, rest, "\"=\", \"!=\", \"?=\", \"<\" or \">\""))?;
+        let value = rest[symbol.len()..].trim();
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return Err(ParseError::at(line, &rest[symbol.len()..], "answer"));
+        }
+        if matches!(relation, Relation::Below | Relation::Above) && value.parse::<i128>().is_err() {
+            return Err(ParseError::at(line, value, "number"));
+        }
+        Ok(Fact { part, relation: *relation, value: value.to_string() })
+    }
+
+    pub fn holds_for(&self, answer: &str) -> bool {
+        match self.relation {
+            Relation::Is | Relation::Computed => answer == self.value,
+            Relation::IsNot => answer != self.value,
+            Relation::Below => compare(answer, &self.value) == Some(Ordering::Less),
+            Relation::Above => compare(answer, &self.value) == Some(Ordering::Greater),
+        }
+    }
+}
+
+fn compare(answer: &str, bound: &str) -> Option<Ordering> {
+    Some(answer.parse::<i128>().ok()?.cmp(&bound.parse::<i128>().ok()?))
+}
+
+impl Display for Fact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = RELATIONS.iter().find(|(_, relation)| *relation == self.relation).unwrap().0;
+        write!(f, "part{} {} {}", self.part, symbol, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "\
+# day12
+part1 < 10613
+part1 != 8273
+part1 = 8180
+
+part2 > 8180
+part2 ?= 9000
+";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.facts.len(), 5);
+        assert_eq!(answers.facts[1], Fact { part: Part::One, relation: Relation::IsNot, value: "8273".to_string() });
+        assert_eq!(answers.facts[1].to_string(), "part1 != 8273");
+        assert_eq!(answers.expected(Part::One), Some("8180"));
+        assert_eq!(answers.expected(Part::Two), None);
+        assert_eq!(answers.computed(Part::Two), Some("9000"));
+        assert_eq!(answers.facts[4].to_string(), "part2 ?= 9000");
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.contradiction(Part::One, "8180"), None);
+        assert_eq!(answers.contradiction(Part::One, "10613").unwrap().to_string(), "part1 < 10613");
+        assert_eq!(answers.contradiction(Part::One, "8273").unwrap().to_string(), "part1 != 8273");
+        assert_eq!(answers.contradiction(Part::Two, "8180").unwrap().to_string(), "part2 > 8180");
+        assert_eq!(answers.contradiction(Part::Two, "x").unwrap().to_string(), "part2 > 8180");
+        // a computed answer is no verdict, only a regression check
+        assert_eq!(answers.contradiction(Part::Two, "9001"), None);
+        assert_eq!(answers.regression(Part::Two, "9001").unwrap().to_string(), "part2 ?= 9000");
+        assert_eq!(answers.regression(Part::Two, "9000"), None);
+        assert_eq!(answers.regression(Part::One, "8273").unwrap().to_string(), "part1 != 8273");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Answers::parse("part1 = 1\n\npart3 = 2\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        let e = Answers::parse("part1 ~ 2").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 7, "\"=\", \"!=\", \"?=\", \"<\" or \">\""));
+        let e = Answers::parse("part2 < abc").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 9, "number"));
+    }
+}
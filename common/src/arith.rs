@@ -0,0 +1,181 @@
+/*
+arithmetic for answers that can outgrow their type. by default these are the plain operators; with the
+checked-arithmetic feature an overflow is recorded instead, and Solver::run reports it as an error naming
+the day and operation. wide-arithmetic makes every Wide<T> an i128, to recompute the inputs that overflow
+ */
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Sub};
+
+/*
+integer types the days compute answers in
+ */
+pub trait Checked: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+    fn try_from_usize(n: usize) -> Option<Self>;
+    // n as Self, keeping only the bits that fit
+    fn from_usize(n: usize) -> Self;
+}
+
+/*
+T, or i128 when built with wide-arithmetic
+ */
+pub trait Widen {
+    type Wide: Checked + Display;
+}
+
+pub type Wide<T> = <T as Widen>::Wide;
+
+macro_rules! integers {
+    ($($t:ty)*) => {
+        $(
+            impl Checked for $t {
+                const ZERO: $t = 0;
+
+                fn checked_add(self, rhs: $t) -> Option<$t> { <$t>::checked_add(self, rhs) }
+                fn checked_sub(self, rhs: $t) -> Option<$t> { <$t>::checked_sub(self, rhs) }
+                fn checked_mul(self, rhs: $t) -> Option<$t> { <$t>::checked_mul(self, rhs) }
+                fn checked_pow(self, exp: u32) -> Option<$t> { <$t>::checked_pow(self, exp) }
+                fn pow(self, exp: u32) -> $t { <$t>::pow(self, exp) }
+                fn try_from_usize(n: usize) -> Option<$t> { <$t>::try_from(n).ok() }
+                fn from_usize(n: usize) -> $t { n as $t }
+            }
+        )*
+    };
+}
+
+macro_rules! widen {
+    ($($t:ty)*) => {
+        $(
+            impl Widen for $t {
+                #[cfg(not(feature = "wide-arithmetic"))]
+                type Wide = $t;
+                #[cfg(feature = "wide-arithmetic")]
+                type Wide = i128;
+            }
+        )*
+    };
+}
+
+integers!(u8 i32 i64 u64 usize i128 u128);
+// u128 holds more than i128 already, it is not widened
+widen!(u8 i32 i64 u64 usize i128);
+
+/*
+an answer that did not fit the type it is computed in
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overflow {
+    pub day: u8,
+    pub operation: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{} overflowed computing {}", self.day, self.operation)?;
+        if cfg!(not(feature = "wide-arithmetic")) {
+            write!(f, ", build with --features wide-arithmetic to recompute it in i128")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+thread_local! {
+    // the first operation that overflowed since the last take_overflow
+    static OVERFLOW: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/*
+the first overflow recorded on this thread since the last call, clearing it
+ */
+pub fn take_overflow() -> Option<&'static str> {
+    OVERFLOW.with(Cell::take)
+}
+
+// the checked result, or with the feature off the unchecked one; after an overflow the value is meaningless
+#[cfg(feature = "checked-arithmetic")]
+fn checked<T>(operation: &'static str, result: impl FnOnce() -> Option<T>, _unchecked: impl FnOnce() -> T, fallback: T) -> T {
+    result().unwrap_or_else(|| {
+        OVERFLOW.with(|overflow| overflow.set(overflow.get().or(Some(operation))));
+        fallback
+    })
+}
+
+#[cfg(not(feature = "checked-arithmetic"))]
+fn checked<T>(_operation: &'static str, _result: impl FnOnce() -> Option<T>, unchecked: impl FnOnce() -> T, _fallback: T) -> T {
+    unchecked()
+}
+
+pub fn add<T: Checked>(operation: &'static str, a: T, b: T) -> T {
+    checked(operation, || a.checked_add(b), || a + b, a)
+}
+
+pub fn sub<T: Checked>(operation: &'static str, a: T, b: T) -> T {
+    checked(operation, || a.checked_sub(b), || a - b, a)
+}
+
+pub fn mul<T: Checked>(operation: &'static str, a: T, b: T) -> T {
+    checked(operation, || a.checked_mul(b), || a * b, a)
+}
+
+pub fn pow<T: Checked>(operation: &'static str, base: T, exp: u32) -> T {
+    checked(operation, || base.checked_pow(exp), || base.pow(exp), base)
+}
+
+pub fn sum<T: Checked>(operation: &'static str, values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, |total, value| add(operation, total, value))
+}
+
+/*
+a count as T, which unchecked keeps only the low bits like `as` does
+ */
+pub fn from_count<T: Checked>(operation: &'static str, n: usize) -> T {
+    checked(operation, || T::try_from_usize(n), || T::from_usize(n), T::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_range() {
+        assert_eq!(add("a", 2_i32, 3), 5);
+        assert_eq!(sub("a", 2_u64, 2), 0);
+        assert_eq!(mul("a", -4_i64, 3), -12);
+        assert_eq!(pow("a", 2_i32, 10), 1024);
+        assert_eq!(sum("a", [1_i32, 2, 3]), 6);
+        assert_eq!(from_count::<u8>("a", 255), 255);
+        assert_eq!(take_overflow(), None);
+    }
+
+    #[cfg(feature = "checked-arithmetic")]
+    #[test]
+    fn test_overflow_recorded() {
+        assert_eq!(from_count::<u8>("tiles in a row", 256), 0);
+        assert_eq!(sum("calibration values", [i32::MAX, 1]), i32::MAX);
+        assert_eq!(take_overflow(), Some("tiles in a row"));
+        assert_eq!(take_overflow(), None);
+        pow("points", 2_i32, 31);
+        assert_eq!(take_overflow(), Some("points"));
+    }
+
+    #[cfg(not(feature = "checked-arithmetic"))]
+    #[test]
+    fn test_unchecked_narrowing() {
+        assert_eq!(from_count::<u8>("tiles in a row", 300), 44);
+    }
+
+    #[test]
+    fn test_wide() {
+        let wide = std::any::type_name::<Wide<i32>>();
+        assert_eq!(wide, if cfg!(feature = "wide-arithmetic") { "i128" } else { "i32" });
+    }
+}
@@ -0,0 +1,35 @@
+use criterion::{black_box, Criterion};
+use crate::{parse_input, Solution};
+
+/*
+criterion benchmarks for a day, grouped as dayN/parse, dayN/part1 and dayN/part2
+ */
+pub fn bench_solution<S: Solution>(c: &mut Criterion, input: &str) {
+    bench_parse::<S>(c, input);
+    bench_part1::<S>(c, input);
+    bench_part2::<S>(c, input);
+}
+
+pub fn bench_parse<S: Solution>(c: &mut Criterion, input: &str) {
+    c.bench_function(&format!("day{}/parse", S::DAY), |b| b.iter(||
+        parse_input::<S>(black_box(input))
+    ));
+}
+
+pub fn bench_part1<S: Solution>(c: &mut Criterion, input: &str) {
+    let input = parsed::<S>(input);
+    c.bench_function(&format!("day{}/part1", S::DAY), |b| b.iter(||
+        S::part1(black_box(&input))
+    ));
+}
+
+pub fn bench_part2<S: Solution>(c: &mut Criterion, input: &str) {
+    let input = parsed::<S>(input);
+    c.bench_function(&format!("day{}/part2", S::DAY), |b| b.iter(||
+        S::part2(black_box(&input))
+    ));
+}
+
+fn parsed<S: Solution>(input: &str) -> S::Input {
+    parse_input::<S>(input).unwrap_or_else(|e| panic!("{}", e.render("input.txt")))
+}
@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+use std::io::Read;
+use std::{fs, io};
+
+/*
+puzzle input text together with what to call it in diagnostics
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+/*
+the file at path, stdin for "-", or the embedded input when no path is given
+ */
+pub fn load(path: Option<&str>, embedded: Option<&'static str>) -> Result<Input, String> {
+    match (path, embedded) {
+        (Some("-"), _) => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|e| format!("cannot read <stdin>: {e}"))?;
+            Ok(Input { name: "<stdin>".to_string(), text })
+        }
+        (Some(path), _) => fs::read_to_string(path)
+            .map(|text| Input { name: path.to_string(), text })
+            .map_err(|e| format!("cannot read {path}: {e}")),
+        (None, Some(text)) => Ok(Input { name: "input.txt".to_string(), text: text.to_string() }),
+        (None, None) => Err("no input given: pass an input file or - for stdin (or build with the embedded-input feature)".to_string()),
+    }
+}
+
+/*
+the text with LF line endings, no trailing whitespace on any line and exactly one newline at the end,
+so Windows files and stray blank lines parse like the original. borrows when there is nothing to change
+ */
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let clean_lines = !text.contains('\r') && !text.lines().any(|line| line.ends_with(char::is_whitespace));
+    let clean_end = text.is_empty() || (text.ends_with('\n') && !text.ends_with("\n\n") && text != "\n");
+    if clean_lines && clean_end {
+        return Cow::Borrowed(text);
+    }
+    let mut normalized = text.lines().map(str::trim_end).collect::<Vec<_>>().join("\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+/*
+the same puzzle text saved the ways it turns up in practice, for tests that every day reads them alike
+ */
+pub fn line_ending_variants(text: &str) -> [(&'static str, String); 4] {
+    let lf = normalize(text).into_owned();
+    [
+        ("LF", lf.clone()),
+        ("CRLF", lf.replace('\n', "\r\n")),
+        ("trailing blank line", format!("{lf}\n")),
+        ("CRLF, trailing spaces and blank lines", lf.replace('\n', "  \r\n") + "\r\n \r\n"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_file() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "1 2 3\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(load(Some(path), Some("embedded")), Ok(Input { name: path.to_string(), text: "1 2 3\n".to_string() }));
+        fs::remove_file(path).unwrap();
+        assert!(load(Some(path), None).unwrap_err().starts_with(&format!("cannot read {path}: ")));
+    }
+
+    #[test]
+    fn test_load_embedded() {
+        assert_eq!(load(None, Some("1 2 3\n")), Ok(Input { name: "input.txt".to_string(), text: "1 2 3\n".to_string() }));
+        assert!(load(None, None).is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("1 2\n\n3\n"), Cow::Borrowed("1 2\n\n3\n")));
+        assert!(matches!(normalize(""), Cow::Borrowed("")));
+        assert_eq!(normalize("1 2\r\n\r\n3\r\n"), "1 2\n\n3\n");
+        assert_eq!(normalize("1 2  \n3\t\n\n \n"), "1 2\n3\n");
+        assert_eq!(normalize("1 2"), "1 2\n");
+        assert_eq!(normalize("\n\r\n"), "");
+    }
+
+    #[test]
+    fn test_line_ending_variants() {
+        for (name, variant) in line_ending_variants("1 2\n\n3") {
+            assert_eq!(normalize(&variant), "1 2\n\n3\n", "{name}");
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use arith::Overflow;
+
+pub mod answers;
+pub mod arith;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod input;
+pub mod logging;
+pub mod memo;
+pub mod parse;
+
+/*
+one puzzle day: parse the raw input once, then answer both parts from the parsed form
+ */
+pub trait Solution {
+    const DAY: u8;
+    // the day's input.txt, only compiled in with the embedded-input feature
+    const EMBEDDED_INPUT: Option<&'static str> = None;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    // for days that leave unusable lines out of an answer: which ones part left out, None when it used them all
+    fn rejections(_input: &Self::Input, _part: Part) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/*
+object safe view of a Solution, so days with different Input/Part types can sit in one table
+ */
+pub trait Solver {
+    fn day(&self) -> u8;
+    fn embedded_input(&self) -> Option<&'static str>;
+    fn run(&self, input: &str, part: Part) -> Result<Run, SolveError>;
+
+    fn solve(&self, input: &str, part: Part) -> Result<String, SolveError> {
+        self.run(input, part).map(|run| run.answer)
+    }
+}
+
+/*
+one timed solve: the answer, the Rust type it was computed in and how long parsing and solving each took
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answer: String,
+    pub answer_type: &'static str,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/*
+parse with S after normalizing line endings and trailing whitespace, which is how every runner reads input
+ */
+pub fn parse_input<S: Solution>(text: &str) -> Result<S::Input, ParseError> {
+    S::parse(&input::normalize(text))
+}
+
+impl<S: Solution> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn embedded_input(&self) -> Option<&'static str> {
+        S::EMBEDDED_INPUT
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Run, SolveError> {
+        let start = Instant::now();
+        let input = parse_input::<S>(input)?;
+        let parse_time = start.elapsed();
+        arith::take_overflow();
+        let start = Instant::now();
+        let (answer, answer_type) = match part {
+            Part::One => (S::part1(&input).to_string(), std::any::type_name::<S::Part1>()),
+            Part::Two => (S::part2(&input).to_string(), std::any::type_name::<S::Part2>()),
+        };
+        if let Some(operation) = arith::take_overflow() {
+            return Err(SolveError::Overflow(Overflow { day: S::DAY, operation }));
+        }
+        Ok(Run { answer, answer_type, parse_time, solve_time: start.elapsed() })
+    }
+}
+
+/*
+where and why an input could not be parsed, with 1-based line and column like a compiler diagnostic
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    pub source_line: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        ParseError { line, column, expected: expected.into(), found: String::new(), source_line: None }
+    }
+
+    /*
+    error at position, which must be a sub-slice of source (the rest of the input where parsing stopped)
+     */
+    pub fn at(source: &str, position: &str, expected: impl Into<String>) -> Self {
+        let offset = (position.as_ptr() as usize).saturating_sub(source.as_ptr() as usize).min(source.len());
+        ParseError::at_offset(source, offset, expected)
+    }
+
+    pub fn at_offset(source: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        let line_end = source[offset..].find('\n').map(|idx| offset + idx).unwrap_or(source.len());
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            expected: expected.into(),
+            found: describe_found(&source[offset..]),
+            source_line: Some(source[line_start..line_end].trim_end_matches('\r').to_string()),
+        }
+    }
+
+    /*
+    the same error, for a line that sits lines further down in the whole input
+     */
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /*
+    multi-line diagnostic pointing at the offending column, name is what the input is called (usually its path)
+     */
+    pub fn render(&self, name: &str) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let mut rendered = format!("error: {}\n{gutter}--> {name}:{}:{}\n", self.summary(), self.line, self.column);
+        if let Some(source_line) = &self.source_line {
+            rendered += &format!("{gutter} |\n");
+            rendered += &format!("{} | {}\n", self.line, source_line);
+            rendered += &format!("{gutter} | {}^\n", " ".repeat(self.column - 1));
+        }
+        rendered
+    }
+
+    fn summary(&self) -> String {
+        if self.found.is_empty() {
+            format!("expected {}", self.expected)
+        } else {
+            format!("expected {}, found {}", self.expected, self.found)
+        }
+    }
+}
+
+fn describe_found(rest: &str) -> String {
+    match rest.chars().next() {
+        None => "end of input".to_string(),
+        Some('\n' | '\r') => "end of line".to_string(),
+        Some(c) if c.is_whitespace() => format!("{:?}", c),
+        Some(_) => {
+            let token = rest.split(char::is_whitespace).next().unwrap_or(rest);
+            format!("{:?}", token.chars().take(16).collect::<String>())
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.summary())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/*
+why a solver gave no answer: its input did not parse, or (with checked-arithmetic) the answer overflowed
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    Parse(ParseError),
+    Overflow(Overflow),
+}
+
+impl SolveError {
+    /*
+    the parse error's diagnostic, or the overflow on one line
+     */
+    pub fn render(&self, name: &str) -> String {
+        match self {
+            SolveError::Parse(e) => e.render(name),
+            SolveError::Overflow(e) => format!("error: {e}\n"),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> SolveError {
+        SolveError::Parse(e)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse(e) => e.fmt(f),
+            SolveError::Overflow(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/*
+shared body of every dayN binary: dayN [--strict | --skip] [<input file> | -]. the lines a part left out of its
+answer are listed on stderr, and with --strict fail that part instead of answering it
+ */
+pub fn run<S: Solution>() {
+    logging::init();
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let strict = match args.first().map(String::as_str) {
+        Some(mode @ ("--strict" | "--skip")) => {
+            let strict = mode == "--strict";
+            args.remove(0);
+            strict
+        }
+        _ => false,
+    };
+    let path = match args.as_slice() {
+        [] => None,
+        [path] => Some(path.as_str()),
+        _ => {
+            eprintln!("usage: day{} [--strict | --skip] [<input file> | -]", S::DAY);
+            std::process::exit(2);
+        }
+    };
+    let input = input::load(path, S::EMBEDDED_INPUT).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(2);
+    });
+    match parse_input::<S>(&input.text) {
+        Ok(input) => {
+            arith::take_overflow();
+            let part1 = S::part1(&input);
+            let rejections1 = S::rejections(&input, Part::One);
+            overflow_exit::<S>();
+            let failed1 = print_answer(Part::One, part1, rejections1, strict);
+            let part2 = S::part2(&input);
+            let rejections2 = S::rejections(&input, Part::Two);
+            overflow_exit::<S>();
+            let failed2 = print_answer(Part::Two, part2, rejections2, strict);
+            if failed1 || failed2 {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprint!("{}", e.render(&input.name));
+            std::process::exit(1);
+        }
+    }
+}
+
+// the answer after the lines it left out, or in strict mode only those as an error; true when it failed
+fn print_answer(part: Part, answer: impl Display, rejections: Option<String>, strict: bool) -> bool {
+    match rejections {
+        Some(rejections) if strict => {
+            eprintln!("error: part{part}: {rejections}");
+            return true;
+        }
+        Some(rejections) => eprintln!("part{part}: {rejections}"),
+        None => {}
+    }
+    println!("part{part}: {answer}");
+    false
+}
+
+// an answer that overflowed is garbage, say so and stop before printing it
+fn overflow_exit<S: Solution>() {
+    if let Some(operation) = arith::take_overflow() {
+        eprintln!("error: {}", Overflow { day: S::DAY, operation });
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        const DAY: u8 = 0;
+        type Input = Vec<i32>;
+        type Part1 = i32;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace()
+                .map(|s| s.parse().map_err(|_| ParseError::at(input, s, "number")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> i32 {
+            arith::sum("doubled sum", input.iter().map(|&n| arith::mul("doubling", n, 2)))
+        }
+
+        fn part2(input: &Self::Input) -> String {
+            format!("{:?}", input)
+        }
+    }
+
+    #[test]
+    fn test_solver() {
+        let solver: &dyn Solver = &Doubler;
+        assert_eq!(solver.day(), 0);
+        assert_eq!(solver.solve("1 2 3", Part::One), Ok("12".to_string()));
+        assert_eq!(solver.solve("1 2 3", Part::Two), Ok("[1, 2, 3]".to_string()));
+        assert_eq!(solver.solve("1 x", Part::One), Err(SolveError::Parse(ParseError::at("1 x", &"1 x"[2..], "number"))));
+        assert_eq!(solver.run("1 2 3", Part::One).unwrap().answer_type, "i32");
+        assert_eq!(solver.run("1 2 3", Part::Two).unwrap().answer_type, "alloc::string::String");
+    }
+
+    #[cfg(feature = "checked-arithmetic")]
+    #[test]
+    fn test_overflow() {
+        let overflow = Doubler.solve("1 1073741824 2", Part::One).unwrap_err();
+        assert_eq!(overflow, SolveError::Overflow(Overflow { day: 0, operation: "doubling" }));
+        assert!(overflow.render("input").starts_with("error: day0 overflowed computing doubling"));
+        assert_eq!(Doubler.solve("1073741823 1", Part::One), Err(SolveError::Overflow(Overflow { day: 0, operation: "doubled sum" })));
+        assert_eq!(Doubler.solve("1 2 3", Part::One), Ok("12".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let source = "1 2\n3 x4 5\n";
+        let e = ParseError::at(source, &source[6..], "number");
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.found, "\"x4\"");
+        assert_eq!(e.source_line.as_deref(), Some("3 x4 5"));
+        assert_eq!(e.to_string(), "line 2, column 3: expected number, found \"x4\"");
+        assert_eq!(ParseError::at(source, &source[source.len()..], "number").found, "end of input");
+        assert_eq!(ParseError::at(source, &source[3..], "number").found, "end of line");
+        assert_eq!(ParseError::at(source, &source[1..], "number").found, "' '");
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "1 2\n3 x4 5\n";
+        let e = ParseError::at(source, &source[6..], "number");
+        assert_eq!(e.render("input.txt"), "\
+error: expected number, found \"x4\"
+ --> input.txt:2:3
+  |
+2 | 3 x4 5
+  |   ^
+");
+    }
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Part::from_number(1), Some(Part::One));
+        assert_eq!(Part::from_number(2), Some(Part::Two));
+        assert_eq!(Part::from_number(3), None);
+    }
+}
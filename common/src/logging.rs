@@ -0,0 +1,114 @@
+use std::io::Write;
+use log::{LevelFilter, Log, Metadata, Record};
+
+/*
+environment variable holding the verbosity, e.g. AOC_LOG=debug or AOC_LOG=day12=debug,day12::record=trace
+ */
+pub const ENV_VAR: &str = "AOC_LOG";
+
+/*
+log level per target prefix: a bare level sets the default, day=level or day::module=level narrows it,
+the longest matching prefix wins and everything is off unless asked for
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    default: LevelFilter,
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut filter = Filter { default: LevelFilter::Off, directives: vec![] };
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => filter.directives.push((target.trim().to_string(), parse_level(level)?)),
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        // longest prefix first, so the first match is the most specific
+        filter.directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Ok(filter)
+    }
+
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.directives.iter()
+            .find(|(prefix, _)| target == prefix || target.strip_prefix(prefix.as_str()).is_some_and(|rest| rest.starts_with("::")))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.directives.iter().map(|(_, level)| *level).fold(self.default, std::cmp::max)
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level.trim().parse().map_err(|_| format!("unknown log level {:?}", level.trim()))
+}
+
+struct Logger {
+    filter: Filter,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let _ = writeln!(std::io::stderr().lock(), "[{:<5} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/*
+install the stderr logger configured from AOC_LOG, silent when it is unset
+ */
+pub fn init() {
+    let spec = std::env::var(ENV_VAR).unwrap_or_default();
+    if let Err(e) = init_with(&spec) {
+        eprintln!("{}: {}", ENV_VAR, e);
+    }
+}
+
+pub fn init_with(spec: &str) -> Result<(), String> {
+    let filter = Filter::parse(spec)?;
+    let max_level = filter.max_level();
+    // a logger can only be installed once per process, later calls keep the first one
+    if log::set_boxed_logger(Box::new(Logger { filter })).is_ok() {
+        log::set_max_level(max_level);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silent_by_default() {
+        let filter = Filter::parse("").unwrap();
+        assert_eq!(filter.level_for("day12"), LevelFilter::Off);
+        assert_eq!(filter.max_level(), LevelFilter::Off);
+    }
+
+    #[test]
+    fn test_per_day_and_module() {
+        let filter = Filter::parse("warn, day12=debug ,day12::record=trace,day1=off").unwrap();
+        assert_eq!(filter.level_for("day8"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("day12"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("day12::parsing"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("day12::record"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("day1"), LevelFilter::Off);
+        assert_eq!(filter.level_for("day10"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn test_unknown_level() {
+        assert_eq!(Filter::parse("day12=loud"), Err("unknown log level \"loud\"".to_string()));
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/*
+cache of results by key for recursive functions: the computation gets the memo back,
+so it can recurse through it
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_insert_with(n, |memo| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) })
+    }
+ */
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+    stats: Stats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo { cache: HashMap::new(), capacity: None, stats: Stats::default() }
+    }
+
+    /*
+    keeps at most capacity results, once full new results are computed but not stored
+     */
+    pub fn with_capacity(capacity: usize) -> Self {
+        Memo { capacity: Some(capacity), ..Memo::new() }
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = compute(self);
+        if self.capacity.is_none_or(|capacity| self.cache.len() < capacity) {
+            self.cache.insert(key, value.clone());
+        }
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /*
+    forget every result and reset the statistics
+     */
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = Stats::default();
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses", self.hits, self.misses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_insert_with(n, |memo| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.stats(), Stats { hits: 88, misses: 91 });
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut memo = Memo::with_capacity(10);
+        assert_eq!(fib(&mut memo, 30), 832040);
+        assert_eq!(memo.len(), 10);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut memo = Memo::new();
+        fib(&mut memo, 10);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), Stats::default());
+        assert_eq!(memo.stats().to_string(), "0 hits, 0 misses");
+    }
+}
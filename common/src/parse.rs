@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use nom::error::{ContextError, ErrorKind};
+use nom::{InputLength, Parser};
+use crate::ParseError;
+
+pub type IResult<I, O> = nom::IResult<I, O, Expected<I>>;
+
+/*
+nom error that remembers where parsing stopped and what would have been accepted there
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expected<I> {
+    pub input: I,
+    pub expected: Vec<Cow<'static, str>>,
+}
+
+impl<I> Expected<I> {
+    pub fn new(input: I, expected: impl Into<Cow<'static, str>>) -> Self {
+        Expected { input, expected: vec![expected.into()] }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.expected.as_slice() {
+            [] => "something else".to_string(),
+            [only] => only.to_string(),
+            [init @ .., last] => format!("{} or {}", init.join(", "), last),
+        }
+    }
+
+}
+
+impl Expected<&str> {
+    pub fn into_parse_error(self, source: &str) -> ParseError {
+        ParseError::at(source, self.input, self.describe())
+    }
+}
+
+fn describe(kind: ErrorKind) -> Cow<'static, str> {
+    match kind {
+        ErrorKind::Digit => "number".into(),
+        ErrorKind::Alpha => "letter".into(),
+        ErrorKind::AlphaNumeric => "letter or digit".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".into(),
+        ErrorKind::CrLf => "line ending".into(),
+        ErrorKind::Eof => "end of input".into(),
+        kind => kind.description().to_string().into(),
+    }
+}
+
+impl<I: InputLength> nom::error::ParseError<I> for Expected<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Expected::new(input, describe(kind))
+    }
+
+    // the innermost error is the most precise one
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    // keep the branch that got furthest, and merge branches that failed at the same spot
+    fn or(mut self, other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                other.expected.into_iter().for_each(|e| if !self.expected.contains(&e) { self.expected.push(e) });
+                self
+            }
+        }
+    }
+}
+
+impl<I: InputLength> ContextError<I> for Expected<I> {
+    // a context only names the failure when nothing inside it made progress
+    fn add_context(input: I, context: &'static str, other: Self) -> Self {
+        if other.input.input_len() == input.input_len() {
+            Expected::new(input, context)
+        } else {
+            other
+        }
+    }
+}
+
+/*
+nom's tag, reporting the literal itself as what was expected
+ */
+pub fn tag<'a>(literal: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |input: &'a str| nom::bytes::complete::tag(literal)(input)
+        .map_err(|e| e.map(|_: Expected<&str>| Expected::new(input, format!("{:?}", literal))))
+}
+
+/*
+run parser over the whole source, allowing only trailing whitespace after it
+ */
+pub fn complete<'a, O>(mut parser: impl Parser<&'a str, O, Expected<&'a str>>, source: &'a str) -> Result<O, ParseError> {
+    match parser.parse(source) {
+        Ok((remaining, output)) if remaining.trim().is_empty() => Ok(output),
+        Ok((remaining, _)) => Err(ParseError::at(source, remaining.trim_start(), "end of input")),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(e.into_parse_error(source)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(source, &source[source.len()..], "more input")),
+    }
+}
+
+/*
+parse every line on its own, reporting errors with their line number in source
+ */
+pub fn lines<O>(source: &str, parse_line: impl Fn(&str) -> Result<O, ParseError>) -> Result<Vec<O>, ParseError> {
+    source.lines().enumerate()
+        .map(|(idx, line)| parse_line(line).map_err(|e| e.below(idx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::branch::alt;
+    use nom::character::complete::{i32, space1};
+    use nom::error::context;
+    use nom::multi::separated_list1;
+    use nom::sequence::preceded;
+    use super::*;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<i32>> {
+        preceded(tag("n:"), preceded(space1, separated_list1(space1, i32)))(input)
+    }
+
+    #[test]
+    fn test_tag_expected() {
+        let e = complete(numbers, "x: 1 2").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 1, "\"n:\""));
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let e = complete(numbers, "n: 1 2 x").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str(), e.found.as_str()), (1, 8, "end of input", "\"x\""));
+        assert_eq!(complete(numbers, "n: 1 2\n\n"), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_alt_merges_expected() {
+        let colour = alt((tag("red"), tag("blue")));
+        let e = complete(colour, "green").unwrap_err();
+        assert_eq!(e.expected, "\"red\" or \"blue\"");
+    }
+
+    #[test]
+    fn test_context_names_failure() {
+        let e = complete(context("colour", alt((tag("red"), tag("blue")))), "green").unwrap_err();
+        assert_eq!(e.expected, "colour");
+    }
+
+    #[test]
+    fn test_lines() {
+        let e = lines("n: 1\nn: 2\nn: x", |line| complete(numbers, line)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (3, 4, "number"));
+    }
+}
@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use common::arith::Wide;
+use day1::{CalibrationRule, Day1};
+
+fn bench_day1(c: &mut Criterion) {
+    common::bench::bench_solution::<Day1>(c, include_str!("../src/input.txt"));
+}
+
+// size of the synthetic calibration document
+const SYNTHETIC_BYTES: usize = 100 << 20;
+
+const PIECES: [&str; 16] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "oneight", "twone", "x", "qz", "abc", "ninet", "3",
+];
+
+/*
+lines of 20 to 60 bytes of names, overlaps, stray letters and digits, from a fixed xorshift seed so every run
+scans the same document; every line has a numeral so the values are all defined
+ */
+fn synthetic_document() -> String {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut document = String::with_capacity(SYNTHETIC_BYTES + 64);
+    while document.len() < SYNTHETIC_BYTES {
+        let length = 20 + (random() % 40) as usize;
+        let start = document.len();
+        while document.len() - start < length {
+            document += PIECES[(random() % PIECES.len() as u64) as usize];
+        }
+        document.push(char::from(b'1' + (random() % 9) as u8));
+        document.push('\n');
+    }
+    document
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let document = synthetic_document();
+    let matcher = CalibrationRule::part2();
+    let tokens = matcher.clone().without_matcher();
+    let part2 = |rule: &CalibrationRule, document: &str| rule.summarize::<Wide<i32>>(document).total;
+    assert_eq!(part2(&tokens, &document), part2(&matcher, &document));
+
+    let mut group = c.benchmark_group("day1/scan_100MB");
+    group.sample_size(10).throughput(Throughput::Bytes(document.len() as u64));
+    group.bench_function("tokens", |b| b.iter(|| part2(&tokens, black_box(&document))));
+    group.bench_function("matcher", |b| b.iter(|| part2(&matcher, black_box(&document))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_day1, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,246 @@
+use crate::{Token, Vocabulary};
+
+/*
+two Aho-Corasick automata over a vocabulary's words and its numerals: one reads a line forwards to its first
+token, the other reads the reversed words backwards from the end to the last one. neither goes past the other's
+token, so no byte is read twice (bar a few bytes of lookahead), and nothing is allocated while scanning
+ */
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Matcher {
+    pub fn new(vocabulary: &Vocabulary) -> Matcher {
+        let numerals = (0..vocabulary.radix).flat_map(|digit| {
+            let numeral = char::from_digit(digit, vocabulary.radix).expect("a digit below radix");
+            // letter numerals in either case
+            let cases = [numeral, numeral.to_ascii_uppercase()];
+            cases.into_iter().take(if numeral.is_ascii_digit() { 1 } else { 2 }).map(move |case| (vec![case as u8], digit as u8))
+        });
+        let words = vocabulary.words.iter()
+            .flat_map(|(word, digit)| spellings(word, vocabulary.ignore_case).into_iter().map(|spelling| (spelling, *digit)));
+        let words = numerals.chain(words).collect::<Vec<_>>();
+        let reversed = words.iter().map(|(word, digit)| (word.iter().rev().copied().collect(), *digit)).collect::<Vec<_>>();
+        Matcher {
+            forward: Automaton::new(&words, vocabulary.ignore_case),
+            backward: Automaton::new(&reversed, vocabulary.ignore_case),
+        }
+    }
+
+    /*
+    the first and last tokens of line, the same ones Vocabulary::tokens gives from either end
+     */
+    pub fn first_last<'a>(&self, line: &'a str) -> Option<(Token<'a>, Token<'a>)> {
+        let bytes = line.as_bytes();
+        let first = self.forward.first(bytes)?;
+        // the first token is also a last candidate, so this always finds one
+        let last = self.backward.last(bytes, first.0)?;
+        let token = |(offset, len, digit): (usize, usize, u8)| Token { offset, digit, span: &line[offset..offset + len] };
+        Some((token(first), token(last)))
+    }
+}
+
+// no state yet, filled in from the failure links
+const MISSING: u32 = u32::MAX;
+
+/*
+a dense transition table indexed by state and byte class, bytes that occur in no word sharing class 0
+ */
+#[derive(Debug, Clone)]
+struct Automaton {
+    classes: [u16; 256],
+    class_count: usize,
+    next: Vec<u32>,
+    // (length, digit) of the longest word ending in each state
+    longest: Vec<Option<(usize, u8)>>,
+    max_len: usize,
+}
+
+impl Automaton {
+    fn new(words: &[(Vec<u8>, u8)], fold_ascii: bool) -> Automaton {
+        let mut classes = [0; 256];
+        let mut class_count = 1;
+        for &byte in words.iter().flat_map(|(word, _)| word) {
+            if classes[byte as usize] == 0 {
+                classes[byte as usize] = class_count as u16;
+                class_count += 1;
+            }
+        }
+        if fold_ascii {
+            for upper in b'A'..=b'Z' {
+                classes[upper as usize] = classes[upper.to_ascii_lowercase() as usize];
+            }
+        }
+
+        // the trie, then every missing transition filled in breadth first from the failure links
+        let mut next = vec![MISSING; class_count];
+        let mut longest = vec![None];
+        for (word, digit) in words {
+            let mut state = 0;
+            for &byte in word {
+                let edge = state * class_count + classes[byte as usize] as usize;
+                if next[edge] == MISSING {
+                    next[edge] = longest.len() as u32;
+                    next.extend(std::iter::repeat_n(MISSING, class_count));
+                    longest.push(None);
+                }
+                state = next[edge] as usize;
+            }
+            longest[state] = Some((word.len(), *digit));
+        }
+        let mut fail = vec![0; longest.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for edge in &mut next[..class_count] {
+            match *edge {
+                MISSING => *edge = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let fallback = fail[state];
+            // a word ending here is longer than any reached through the failure link
+            longest[state] = longest[state].or(longest[fallback]);
+            for class in 0..class_count {
+                let fallback_next = next[fallback * class_count + class];
+                match next[state * class_count + class] {
+                    MISSING => next[state * class_count + class] = fallback_next,
+                    child => {
+                        fail[child as usize] = fallback_next as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        let max_len = words.iter().map(|(word, _)| word.len()).max().unwrap_or(0);
+        Automaton { classes, class_count, next, longest, max_len }
+    }
+
+    fn step(&self, state: usize, byte: u8) -> usize {
+        self.next[state * self.class_count + self.classes[byte as usize] as usize] as usize
+    }
+
+    // (offset, length, digit) of the token starting first, the longest one when several start there
+    fn first(&self, bytes: &[u8]) -> Option<(usize, usize, u8)> {
+        let mut state = 0;
+        let mut first: Option<(usize, usize, u8)> = None;
+        for (idx, &byte) in bytes.iter().enumerate() {
+            // a word starting at or before the best start has ended by now
+            if first.is_some_and(|(start, ..)| idx >= start + self.max_len) {
+                break;
+            }
+            state = self.step(state, byte);
+            if let Some((len, digit)) = self.longest[state] {
+                // the longest word ending here starts earliest; at an equal start the later one is longer
+                let start = idx + 1 - len;
+                if first.is_none_or(|(first_start, ..)| start <= first_start) {
+                    first = Some((start, len, digit));
+                }
+            }
+        }
+        first
+    }
+
+    // the same for the token starting last, reading bytes backwards down to floor with reversed words:
+    // the first word found starts last, and the longest one there is the one wanted
+    fn last(&self, bytes: &[u8], floor: usize) -> Option<(usize, usize, u8)> {
+        let mut state = 0;
+        for idx in (floor..bytes.len()).rev() {
+            state = self.step(state, bytes[idx]);
+            if let Some((len, digit)) = self.longest[state] {
+                return Some((idx, len, digit));
+            }
+        }
+        None
+    }
+}
+
+/*
+the byte strings word is matched as: itself, or ignoring case every combination of upper and lower case.
+ASCII letters are only spelled in lower case, upper case bytes share their classes in the automaton;
+other letters each double the spellings
+ */
+fn spellings(word: &str, ignore_case: bool) -> Vec<Vec<u8>> {
+    if !ignore_case {
+        return vec![word.as_bytes().to_vec()];
+    }
+    word.chars().fold(vec![vec![]], |spellings, c| {
+        if c.is_ascii() {
+            return spellings.into_iter().map(|mut spelling| {
+                spelling.push(c.to_ascii_lowercase() as u8);
+                spelling
+            }).collect();
+        }
+        let mut cases = vec![c];
+        for other in c.to_lowercase().chain(c.to_uppercase()) {
+            if !cases.contains(&other) && c.to_lowercase().eq(other.to_lowercase()) {
+                cases.push(other);
+            }
+        }
+        spellings.iter().flat_map(|spelling| cases.iter().map(move |case| {
+            let mut spelling = spelling.clone();
+            spelling.extend_from_slice(case.encode_utf8(&mut [0; 4]).as_bytes());
+            spelling
+        })).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(vocabulary: &Vocabulary, line: &str) -> Option<(u8, u8)> {
+        Matcher::new(vocabulary).first_last(line).map(|(first, last)| (first.digit, last.digit))
+    }
+
+    #[test]
+    fn test_first_last() {
+        let english = Vocabulary::english();
+        assert_eq!(digits(&english, "xtwone3four"), Some((2, 4)));
+        assert_eq!(digits(&english, "zoneight"), Some((1, 8)));
+        assert_eq!(digits(&english, "7pqrstsixteen"), Some((7, 6)));
+        assert_eq!(digits(&english, "abc"), None);
+        assert_eq!(digits(&Vocabulary::english().in_radix(16), "Cxsix"), Some((12, 6)));
+        let (first, last) = Matcher::new(&english).first_last("é3ñeightwo").unwrap();
+        assert_eq!(first, Token { offset: 2, digit: 3, span: "3" });
+        assert_eq!(last, Token { offset: 9, digit: 2, span: "two" });
+    }
+
+    #[test]
+    fn test_nested_words() {
+        // "en" inside "seven": the first token starts earliest, the last starts latest
+        let vocabulary = Vocabulary::parse("seven = 7\nen = 1\nsevens = 2\n").unwrap();
+        assert_eq!(digits(&vocabulary, "xsevenx"), Some((7, 1)));
+        assert_eq!(digits(&vocabulary, "sevens"), Some((2, 1)));
+        assert_eq!(digits(&vocabulary, "sevensen"), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert_eq!(digits(&Vocabulary::english().ignoring_case(), "ONExtWo"), Some((1, 2)));
+        assert_eq!(digits(&Vocabulary::english(), "ONExtWo"), None);
+        let (first, _) = Matcher::new(&Vocabulary::german().ignoring_case()).first_last("FÜNFx").unwrap();
+        assert_eq!(first.span, "FÜNF");
+        assert_eq!(spellings("Fünf", true), [b"f\xc3\xbcnf".to_vec(), b"f\xc3\x9cnf".to_vec()]);
+    }
+
+    #[test]
+    fn test_same_as_tokens() {
+        let vocabularies = [
+            Vocabulary::english(),
+            Vocabulary::german().ignoring_case(),
+            Vocabulary::french().with("zéro", 0).unwrap(),
+            Vocabulary::english().in_radix(16),
+            Vocabulary::numerals().in_radix(7),
+        ];
+        for vocabulary in &vocabularies {
+            let matcher = Matcher::new(vocabulary);
+            for line in include_str!("input.txt").lines().chain(["Fünfundzwanzig", "deuxzérounze", "sevenine"]) {
+                let mut tokens = vocabulary.tokens(line);
+                let expected = tokens.next().map(|first| (first, tokens.next_back().unwrap_or(first)));
+                assert_eq!(matcher.first_last(line), expected, "{line}");
+            }
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use common::arith::{self, Checked, Wide};
+use common::{ParseError, Part, Solution};
+use log::debug;
+
+pub mod automaton;
+pub mod vocabulary;
+
+pub use automaton::Matcher;
+pub use vocabulary::Vocabulary;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Calibrations;
+    type Part1 = Wide<i32>;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Calibrations, ParseError> {
+        Ok(Calibrations {
+            part1: CalibrationRule::part1().calibrations(input).collect(),
+            part2: CalibrationRule::part2().calibrations(input).collect(),
+        })
+    }
+
+    fn part1(input: &Calibrations) -> Wide<i32> {
+        Summary::of(input.part1.iter().copied()).total
+    }
+
+    fn part2(input: &Calibrations) -> Wide<i32> {
+        Summary::of(input.part2.iter().copied()).total
+    }
+
+    fn rejections(input: &Calibrations, part: Part) -> Option<String> {
+        let calibrations = match part {
+            Part::One => &input.part1,
+            Part::Two => &input.part2,
+        };
+        let rejected = calibrations.iter().zip(1..)
+            .filter(|(calibration, _)| calibration.rejection().is_some())
+            .map(|(calibration, line)| (line, *calibration))
+            .collect::<Vec<_>>();
+        (!rejected.is_empty()).then(|| describe_rejections(&rejected))
+    }
+}
+
+/*
+every line's calibration under each part's rule, worked out once when parsing so that an answer and the lines
+left out of it come from the same pass
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibrations {
+    pub part1: Vec<Calibration<Wide<i32>>>,
+    pub part2: Vec<Calibration<Wide<i32>>>,
+}
+
+pub fn part1(input: &str) -> Wide<i32> {
+    CalibrationRule::part1().summarize(input).total
+}
+
+/*
+part1 with digits also spelled out in vocabulary's words
+ */
+pub fn part2(input: &str, vocabulary: &Vocabulary) -> Wide<i32> {
+    CalibrationRule::ends(vocabulary.clone()).summarize(input).total
+}
+
+/*
+which of a line's digits make its calibration value
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Digits {
+    // the first k then the last k, which share digits on lines with fewer than 2k; k is at least 1
+    Ends(usize),
+    All,
+}
+
+/*
+how a line comes to its value: the chosen digits, read with vocabulary, written one after the other in
+vocabulary's radix. the puzzle's rule takes the first and last decimal digit
+ */
+#[derive(Debug, Clone)]
+pub struct CalibrationRule {
+    // private, the matcher is compiled from them
+    digits: Digits,
+    vocabulary: Vocabulary,
+    // compiled for Digits::Ends(1), which only needs the ends of each line
+    matcher: Option<Matcher>,
+}
+
+impl CalibrationRule {
+    pub fn new(vocabulary: Vocabulary, digits: Digits) -> Result<CalibrationRule, String> {
+        if digits == Digits::Ends(0) {
+            return Err("Digits::Ends takes at least one digit from each end, not 0".to_string());
+        }
+        let matcher = (digits == Digits::Ends(1)).then(|| Matcher::new(&vocabulary));
+        Ok(CalibrationRule { digits, vocabulary, matcher })
+    }
+
+    /*
+    the first and last digit read with vocabulary, the puzzle's rule
+     */
+    pub fn ends(vocabulary: Vocabulary) -> CalibrationRule {
+        let matcher = Some(Matcher::new(&vocabulary));
+        CalibrationRule { digits: Digits::Ends(1), vocabulary, matcher }
+    }
+
+    pub fn part1() -> CalibrationRule {
+        CalibrationRule::ends(Vocabulary::numerals())
+    }
+
+    pub fn part2() -> CalibrationRule {
+        CalibrationRule::ends(Vocabulary::english())
+    }
+
+    pub fn digits(&self) -> Digits {
+        self.digits
+    }
+
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    /*
+    the same rule finding every line's digits with Vocabulary::tokens, for comparing against the matcher
+     */
+    pub fn without_matcher(self) -> CalibrationRule {
+        CalibrationRule { matcher: None, ..self }
+    }
+
+    pub fn calibrate<T: Checked>(&self, line: &str) -> Calibration<T> {
+        if line.trim().is_empty() {
+            return Calibration::Empty;
+        }
+        if let Some(matcher) = &self.matcher {
+            let first_last = matcher.first_last(line);
+            debug!("{} -> {:?}", line, first_last);
+            return match first_last {
+                Some((first, last)) => self.value([first.digit, last.digit]),
+                None => Calibration::NoDigits,
+            };
+        }
+        let mut tokens = self.vocabulary.tokens(line).map(|token| token.digit);
+        let digits = match self.digits {
+            Digits::Ends(k) => {
+                // k from each end, without scanning the middle of the line
+                let mut digits = tokens.by_ref().take(k).collect::<Vec<_>>();
+                let mut last = tokens.rev().take(k).collect::<Vec<_>>();
+                // on a line of fewer than 2k digits the last k start among the first
+                let shared = k.saturating_sub(last.len()).min(digits.len());
+                last.extend(digits[digits.len() - shared..].iter().rev());
+                digits.extend(last.into_iter().rev());
+                digits
+            }
+            Digits::All => tokens.collect(),
+        };
+        debug!("{} -> {:?}", line, digits);
+        match self.digits {
+            _ if digits.is_empty() => Calibration::NoDigits,
+            Digits::Ends(k) if digits.len() < 2 * k => Calibration::TooFewDigits,
+            _ => self.value(digits),
+        }
+    }
+
+    // digits as a number in the vocabulary's radix
+    fn value<T: Checked>(&self, digits: impl IntoIterator<Item = u8>) -> Calibration<T> {
+        let radix = T::from_usize(self.vocabulary.radix as usize);
+        digits.into_iter()
+            .try_fold(T::ZERO, |value, digit| value.checked_mul(radix)?.checked_add(T::from_usize(digit as usize)))
+            .map_or(Calibration::TooLarge, Calibration::Value)
+    }
+
+    /*
+    every line's calibration value, or why it has none
+     */
+    pub fn calibrations<'a, T: Checked>(&'a self, input: &'a str) -> impl Iterator<Item = Calibration<T>> + 'a {
+        input.lines().map(|line| self.calibrate(line))
+    }
+
+    pub fn summarize<T: Checked>(&self, input: &str) -> Summary<T> {
+        Summary::of(self.calibrations(input))
+    }
+}
+
+/*
+what one line of a calibration document comes to
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calibration<T> {
+    Value(T),
+    Empty,
+    NoDigits,
+    // fewer than the k digits of Digits::Ends(k)
+    TooFewDigits,
+    // more than T holds
+    TooLarge,
+}
+
+impl<T> Calibration<T> {
+    /*
+    why the line has no value, or None when it has one
+     */
+    pub fn rejection(&self) -> Option<&'static str> {
+        match self {
+            Calibration::Value(_) => None,
+            Calibration::Empty => Some("empty"),
+            Calibration::NoDigits => Some("no digits"),
+            Calibration::TooFewDigits => Some("too few digits"),
+            Calibration::TooLarge => Some("too large"),
+        }
+    }
+}
+
+/*
+the sum of a document's values and the lines, numbered from 1, left out of it
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary<T> {
+    pub total: T,
+    pub rejected: Vec<(usize, Calibration<T>)>,
+}
+
+impl<T: Checked> Summary<T> {
+    pub fn of(calibrations: impl IntoIterator<Item = Calibration<T>>) -> Summary<T> {
+        let mut rejected = vec![];
+        let values = calibrations.into_iter().zip(1..).filter_map(|(calibration, line)| match calibration {
+            Calibration::Value(value) => Some(value),
+            _ => {
+                rejected.push((line, calibration));
+                None
+            }
+        });
+        let total = arith::sum("calibration values", values);
+        Summary { total, rejected }
+    }
+
+    pub fn rejections(&self) -> String {
+        describe_rejections(&self.rejected)
+    }
+}
+
+/*
+the rejected line numbers by reason, like "2 lines rejected, empty: 4; no digits: 7"
+ */
+pub fn describe_rejections<T>(rejected: &[(usize, Calibration<T>)]) -> String {
+    let mut reasons: Vec<(&str, Vec<String>)> = vec![];
+    for (line, calibration) in rejected {
+        let reason = calibration.rejection().expect("only rejected lines");
+        match reasons.iter_mut().find(|(other, _)| *other == reason) {
+            Some((_, lines)) => lines.push(line.to_string()),
+            None => reasons.push((reason, vec![line.to_string()])),
+        }
+    }
+    let reasons = reasons.iter().map(|(reason, lines)| format!("{reason}: {}", lines.join(", "))).collect::<Vec<_>>();
+    let count = rejected.len();
+    format!("{count} line{} rejected, {}", if count == 1 { "" } else { "s" }, reasons.join("; "))
+}
+
+/*
+a digit in a calibration line, written as a numeral or spelled out; offset is where span starts in the line
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub offset: usize,
+    pub digit: u8,
+    pub span: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_part2() {
+        let input =
+"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+fivezg8jmf6hrxnhgxxttwoneg
+";
+        let expected = vec![29, 83, 13, 24, 42, 14, 76, 51];
+
+        input.lines().map(|line| part2(line, &Vocabulary::english())).zip(expected).for_each(|(actual, expected)| {
+            assert_eq!(actual, expected);
+        });
+    }
+
+    #[test]
+    fn test_part2_single() {
+        assert_eq!(part2("two1nine", &Vocabulary::english()), 29);
+        assert_eq!(part2("xx7yy", &Vocabulary::english()), 77);
+        assert_eq!(part2("zweiundvierzig", &Vocabulary::german()), 24);
+        assert_eq!(part2("zero4", &Vocabulary::english().with("zero", 0).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_repeated_last_word() {
+        // the last digit is the second "two", after the "three" that follows the first
+        assert_eq!(part2("one2twothreetwo", &Vocabulary::english()), 12);
+        assert_eq!(part2("6sevenfourseven", &Vocabulary::english()), 67);
+    }
+
+    #[test]
+    fn test_tokens() {
+        let english = Vocabulary::english();
+        let tokens = english.tokens("4nineightwo").collect::<Vec<_>>();
+        assert_eq!(tokens, [
+            Token { offset: 0, digit: 4, span: "4" },
+            Token { offset: 1, digit: 9, span: "nine" },
+            Token { offset: 4, digit: 8, span: "eight" },
+            Token { offset: 8, digit: 2, span: "two" },
+        ]);
+        assert_eq!(english.tokens("é3ñone").map(|token| (token.offset, token.digit)).collect::<Vec<_>>(), [(2, 3), (5, 1)]);
+        assert_eq!(english.tokens("abc").next(), None);
+    }
+
+    #[test]
+    fn test_calibrations() {
+        let input = "1abc2\n\npqr3stu8vwx\nabc\n  \nseven\n";
+        let rule = CalibrationRule::part1();
+        assert_eq!(rule.calibrations::<i32>(input).collect::<Vec<_>>(), [
+            Calibration::Value(12),
+            Calibration::Empty,
+            Calibration::Value(38),
+            Calibration::NoDigits,
+            Calibration::Empty,
+            Calibration::NoDigits,
+        ]);
+        let summary = rule.summarize::<i32>(input);
+        assert_eq!(summary.total, 50);
+        assert_eq!(summary.rejected.iter().map(|(line, _)| *line).collect::<Vec<_>>(), [2, 4, 5, 6]);
+        assert_eq!(summary.rejections(), "4 lines rejected, empty: 2, 5; no digits: 4, 6");
+        assert_eq!(rule.summarize::<i32>("x\n").rejections(), "1 line rejected, no digits: 1");
+    }
+
+    #[test]
+    fn test_rejections() {
+        let input = Day1::parse("1abc2\n\nseven\n").unwrap();
+        assert_eq!((Day1::part1(&input), Day1::part2(&input)), (12, 89));
+        assert_eq!(Day1::rejections(&input, Part::One).as_deref(), Some("2 lines rejected, empty: 2; no digits: 3"));
+        assert_eq!(Day1::rejections(&input, Part::Two).as_deref(), Some("1 line rejected, empty: 2"));
+        assert_eq!(Day1::rejections(&Day1::parse("1abc2\n").unwrap(), Part::One), None);
+    }
+
+    #[test]
+    fn test_skipped_lines() {
+        assert_eq!(part1("1abc2\n\nabc\ntreb7uchet\n"), 89);
+        assert_eq!(part2("two1nine\n\nxyz\n", &Vocabulary::english()), 29);
+        assert_eq!(CalibrationRule::part2().without_matcher().summarize::<i32>("\nseven\n").total, 77);
+    }
+
+    #[test]
+    fn test_rules() {
+        let line = "a1two3fourb5";
+        let value = |vocabulary: Vocabulary, digits| CalibrationRule::new(vocabulary, digits).unwrap().calibrate::<u64>(line);
+        assert_eq!(value(Vocabulary::numerals(), Digits::Ends(1)), Calibration::Value(15));
+        assert_eq!(value(Vocabulary::english(), Digits::Ends(2)), Calibration::Value(1245));
+        assert_eq!(value(Vocabulary::english(), Digits::Ends(4)), Calibration::Value(12342345));
+        assert_eq!(value(Vocabulary::english(), Digits::Ends(6)), Calibration::TooFewDigits);
+        assert_eq!(value(Vocabulary::english(), Digits::All), Calibration::Value(12345));
+        assert_eq!(value(Vocabulary::numerals().in_radix(16), Digits::Ends(1)), Calibration::Value(0xa5));
+        assert_eq!(value(Vocabulary::numerals().in_radix(16), Digits::All), Calibration::Value(0xa13fb5));
+        // in binary only the 1 is a digit, not two, 3, four or 5
+        assert_eq!(value(Vocabulary::english().in_radix(2), Digits::All), Calibration::Value(1));
+    }
+
+    #[test]
+    fn test_no_ends() {
+        let e = CalibrationRule::new(Vocabulary::numerals(), Digits::Ends(0)).unwrap_err();
+        assert_eq!(e, "Digits::Ends takes at least one digit from each end, not 0");
+    }
+
+    #[test]
+    fn test_rule_parts() {
+        let rule = CalibrationRule::new(Vocabulary::german(), Digits::All).unwrap();
+        assert_eq!((rule.digits(), rule.vocabulary()), (Digits::All, &Vocabulary::german()));
+        assert_eq!(rule.calibrate::<u64>("drei7eins"), Calibration::Value(371));
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let rule = CalibrationRule::new(Vocabulary::numerals().in_radix(16), Digits::All).unwrap();
+        let line = "ffffffffffffffff";
+        assert_eq!(rule.calibrate::<u64>(line), Calibration::Value(u64::MAX));
+        assert_eq!(rule.calibrate::<u64>(&format!("1{line}")), Calibration::TooLarge);
+        assert_eq!(rule.calibrate::<u128>(&format!("1{line}")), Calibration::Value(1 << 64 | u64::MAX as u128));
+        let summary = rule.summarize::<u128>(&format!("{line}\n\n{line}{line}1\n"));
+        assert_eq!(summary.total, u64::MAX as u128);
+        assert_eq!(summary.rejections(), "2 lines rejected, empty: 2; too large: 3");
+    }
+
+    // every pair of names where the end of one starts the next
+    const OVERLAPS: [(&str, u8, u8, usize); 8] = [
+        ("oneight", 1, 8, 2),
+        ("twone", 2, 1, 2),
+        ("threeight", 3, 8, 4),
+        ("fiveight", 5, 8, 3),
+        ("sevenine", 7, 9, 4),
+        ("eightwo", 8, 2, 4),
+        ("eighthree", 8, 3, 4),
+        ("nineight", 9, 8, 3),
+    ];
+
+    #[test]
+    fn test_overlaps() {
+        let english = Vocabulary::english();
+        for (word, first, second, offset) in OVERLAPS {
+            let tokens = english.tokens(word).map(|token| (token.offset, token.digit)).collect::<Vec<_>>();
+            assert_eq!(tokens, [(0, first), (offset, second)], "{word}");
+            assert_eq!(english.tokens(word).next_back().map(|token| token.digit), Some(second), "{word}");
+            assert_eq!(part2(word, &english), (first * 10 + second) as Wide<i32>, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_overlaps_complete() {
+        let pairs = NAMES.iter().flat_map(|a| NAMES.iter().filter_map(move |b| {
+            (1..a.len().min(b.len())).find(|&k| a.ends_with(&b[..k])).map(|k| format!("{a}{}", &b[k..]))
+        })).collect::<Vec<_>>();
+        assert_eq!(pairs, OVERLAPS.map(|(word, ..)| word));
+    }
+
+    const NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    /*
+    letters, digits and spelled digits run together, with at least one plain digit
+     */
+    fn calibration_line() -> impl Strategy<Value = String> {
+        let token = prop_oneof![
+            "[a-z]{1,4}",
+            "[1-9]",
+            prop::sample::select(NAMES.to_vec()).prop_map(str::to_string),
+        ];
+        (prop::collection::vec(token, 0..8), "[1-9]", any::<prop::sample::Index>()).prop_map(|(mut tokens, digit, at)| {
+            tokens.insert(at.index(tokens.len() + 1), digit);
+            tokens.concat()
+        })
+    }
+
+    fn calibration_document() -> impl Strategy<Value = String> {
+        prop::collection::vec(calibration_line(), 1..20).prop_map(|lines| lines.join("\n"))
+    }
+
+    // every digit, plain or spelled, at every offset, so overlapping names all count
+    fn all_digits(line: &str) -> Vec<i32> {
+        (0..line.len()).filter_map(|idx| {
+            let rest = &line[idx..];
+            rest.chars().next().and_then(|c| c.to_digit(10)).map(|digit| digit as i32)
+                .or_else(|| NAMES.iter().position(|name| rest.starts_with(name)).map(|idx| idx as i32 + 1))
+        }).collect()
+    }
+
+    fn calibration_value(digits: &[i32]) -> i32 {
+        digits.first().unwrap() * 10 + digits.last().unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(document in calibration_document()) {
+            let expected = document.lines()
+                .map(|line| calibration_value(&line.chars().filter_map(|c| c.to_digit(10)).map(|d| d as i32).collect::<Vec<_>>()))
+                .sum::<i32>();
+            prop_assert_eq!(part1(&document), expected as Wide<i32>);
+        }
+
+        #[test]
+        fn prop_part2(document in calibration_document()) {
+            let expected = document.lines().map(|line| calibration_value(&all_digits(line))).sum::<i32>();
+            prop_assert_eq!(part2(&document, &Vocabulary::english()), expected as Wide<i32>);
+            let tokens = CalibrationRule::part2().without_matcher().summarize::<Wide<i32>>(&document);
+            prop_assert_eq!(tokens.total, expected as Wide<i32>);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::path::Path;
+use common::ParseError;
+use crate::Token;
+
+const ENGLISH: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const GERMAN: [&str; 9] = ["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"];
+const FRENCH: [&str; 9] = ["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"];
+
+/*
+the words a calibration document spells digits with, besides the numerals of radix which are always read:
+0-9, or with radix 16 also a-f in either case. a file lists one word per line:
+
+    # Dutch
+    nul = 0
+    een = 1
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u8)>,
+    pub ignore_case: bool,
+    pub radix: u32,
+}
+
+impl Vocabulary {
+    /*
+    no words, only the numerals
+     */
+    pub fn numerals() -> Vocabulary {
+        Vocabulary { words: vec![], ignore_case: false, radix: 10 }
+    }
+
+    /*
+    one to nine, the puzzle's own vocabulary
+     */
+    pub fn english() -> Vocabulary {
+        Vocabulary::one_to_nine(ENGLISH)
+    }
+
+    pub fn german() -> Vocabulary {
+        Vocabulary::one_to_nine(GERMAN)
+    }
+
+    pub fn french() -> Vocabulary {
+        Vocabulary::one_to_nine(FRENCH)
+    }
+
+    fn one_to_nine(words: [&str; 9]) -> Vocabulary {
+        let words = words.iter().zip(1..).map(|(word, digit)| (word.to_string(), digit)).collect();
+        Vocabulary { words, ignore_case: false, radix: 10 }
+    }
+
+    /*
+    the same words, also matched in upper or mixed case
+     */
+    pub fn ignoring_case(self) -> Vocabulary {
+        Vocabulary { ignore_case: true, ..self }
+    }
+
+    /*
+    the numerals of radix, 2 to 36, and the words for digits below it: in radix 2 "two" is not a digit
+     */
+    pub fn in_radix(self, radix: u32) -> Vocabulary {
+        assert!((2..=36).contains(&radix), "radix {radix} is not in 2..=36");
+        let words = self.words.into_iter().filter(|&(_, digit)| (digit as u32) < radix).collect();
+        Vocabulary { words, radix, ..self }
+    }
+
+    /*
+    one more word, like ("zero", 0), for a digit below the radix
+     */
+    pub fn with(mut self, word: &str, digit: u8) -> Result<Vocabulary, String> {
+        if digit as u32 >= self.radix {
+            return Err(format!("{word} = {digit} is not a digit in radix {}", self.radix));
+        }
+        self.words.push((word.to_string(), digit));
+        Ok(self)
+    }
+
+    pub fn parse(source: &str) -> Result<Vocabulary, ParseError> {
+        let words = source.lines().enumerate()
+            .map(|(idx, line)| (idx, line.trim_end()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(idx, line)| parse_word(line).map_err(|e| e.below(idx)))
+            .collect::<Result<_, _>>()?;
+        Ok(Vocabulary { words, ignore_case: false, radix: 10 })
+    }
+
+    pub fn load(path: &Path) -> Result<Vocabulary, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Vocabulary::parse(&source).map_err(|e| e.render(&path.display().to_string()))
+    }
+
+    /*
+    every digit in line in order, overlapping ones included: "eightwo" gives 8 at 0 and 2 at 4.
+    it is double ended, next_back scans from the end of the line
+     */
+    pub fn tokens<'v, 'a>(&'v self, line: &'a str) -> impl DoubleEndedIterator<Item = Token<'a>> + use<'v, 'a> {
+        (0..line.len())
+            .filter(|&offset| line.is_char_boundary(offset))
+            .filter_map(move |offset| self.token_at(line, offset))
+    }
+
+    // the longest match at offset, when one word is the start of another; a word over a numeral of the same length
+    fn token_at<'a>(&self, line: &'a str, offset: usize) -> Option<Token<'a>> {
+        let rest = &line[offset..];
+        let first = rest.as_bytes()[0];
+        let numeral = (first as char).to_digit(self.radix).map(|digit| (digit as u8, 1));
+        let (digit, len) = match numeral {
+            // no word starts with 0-9, but one can with a letter numeral like hex e
+            Some(numeral) if first.is_ascii_digit() => numeral,
+            _ => numeral.into_iter()
+                .chain(self.words.iter().filter_map(|(word, digit)| self.prefix_len(rest, word).map(|len| (*digit, len))))
+                .max_by_key(|&(_, len)| len)?,
+        };
+        Some(Token { offset, digit, span: &rest[..len] })
+    }
+
+    // bytes of rest that spell word, which can differ from word's own length when case is ignored
+    fn prefix_len(&self, rest: &str, word: &str) -> Option<usize> {
+        if !self.ignore_case {
+            return rest.starts_with(word).then_some(word.len());
+        }
+        let mut chars = rest.char_indices();
+        for expected in word.chars() {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
+            }
+        }
+        Some(chars.next().map_or(rest.len(), |(idx, _)| idx))
+    }
+}
+
+// word = digit
+fn parse_word(line: &str) -> Result<(String, u8), ParseError> {
+    let (word, digit) = line.split_once('=')
+        .ok_or_else(|| ParseError::at(line, &line[line.len()..], "\"= digit\""))?;
+    let word = word.trim();
+    if word.is_empty() || word.contains(char::is_whitespace) || word.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(ParseError::at(line, line.trim_start(), "a word"));
+    }
+    let digit = digit.trim();
+    match digit.parse::<u8>() {
+        Ok(value) if value <= 9 && digit.len() == 1 => Ok((word.to_string(), value)),
+        _ => Err(ParseError::at(line, digit, "digit 0-9")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(vocabulary: &Vocabulary, line: &str) -> Vec<(usize, u8)> {
+        vocabulary.tokens(line).map(|token| (token.offset, token.digit)).collect()
+    }
+
+    #[test]
+    fn test_builtin() {
+        assert_eq!(digits(&Vocabulary::german(), "xfünfzehn3acht"), [(1, 5), (10, 3), (11, 8)]);
+        assert_eq!(digits(&Vocabulary::french(), "deuxunsept"), [(0, 2), (4, 1), (6, 7)]);
+        assert_eq!(digits(&Vocabulary::english(), "zeroone"), [(4, 1)]);
+        assert_eq!(digits(&Vocabulary::english().with("zero", 0).unwrap(), "zeroone"), [(0, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert_eq!(digits(&Vocabulary::english(), "ONEtwo"), [(3, 2)]);
+        assert_eq!(digits(&Vocabulary::english().ignoring_case(), "ONEtWo"), [(0, 1), (3, 2)]);
+        let tokens = Vocabulary::german().ignoring_case().tokens("FÜNFx").collect::<Vec<_>>();
+        assert_eq!(tokens, [Token { offset: 0, digit: 5, span: "FÜNF" }]);
+    }
+
+    #[test]
+    fn test_radix() {
+        assert_eq!(digits(&Vocabulary::numerals().in_radix(16), "x9aFg"), [(1, 9), (2, 10), (3, 15)]);
+        assert_eq!(digits(&Vocabulary::numerals().in_radix(2), "102"), [(0, 1), (1, 0)]);
+        // words for digits beyond the radix are dropped
+        let binary = Vocabulary::english().in_radix(2);
+        assert_eq!(binary.words, [("one".to_string(), 1)]);
+        assert_eq!(digits(&binary, "one0two1"), [(0, 1), (3, 0), (7, 1)]);
+        assert_eq!(digits(&binary.with("nul", 0).unwrap(), "nulone"), [(0, 0), (3, 1)]);
+        // a word is longer than the letter numeral it starts with
+        assert_eq!(digits(&Vocabulary::english().in_radix(16), "eightd"), [(0, 8), (5, 13)]);
+    }
+
+    #[test]
+    fn test_word_beyond_radix() {
+        let e = Vocabulary::numerals().in_radix(2).with("zwei", 2).unwrap_err();
+        assert_eq!(e, "zwei = 2 is not a digit in radix 2");
+    }
+
+    #[test]
+    fn test_longest_match() {
+        let vocabulary = Vocabulary::parse("sept = 7\nseptante = 7\nsepta = 3\n").unwrap();
+        assert_eq!(vocabulary.tokens("septante").map(|token| token.span).collect::<Vec<_>>(), ["septante"]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("# Dutch\nnul = 0\n\neen=1\n").unwrap();
+        let words = vec![("nul".to_string(), 0), ("een".to_string(), 1)];
+        assert_eq!(vocabulary, Vocabulary { words, ignore_case: false, radix: 10 });
+
+        let e = Vocabulary::parse("nul = 0\neen = 10\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 7, "digit 0-9"));
+        let e = Vocabulary::parse("twee 2\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 7, "\"= digit\""));
+        let e = Vocabulary::parse("\ntwee drie = 2\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 1, "a word"));
+    }
+}
@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day10::Day10;
+
+fn bench_day10(c: &mut Criterion) {
+    common::bench::bench_solution::<Day10>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day10);
+criterion_main!(benches);
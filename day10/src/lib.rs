@@ -1,19 +1,59 @@
 use std::collections::HashSet;
-use std::ops::DerefMut;
-use crate::Direction::*;
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
+use grid::{Direction, Grid, Pos};
+use grid::Direction::*;
+use log::debug;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        parse_maze(input)
+    }
 
-pub fn part1(input: &str) -> usize {
-    let grid = as_bytes(input);
-    let start = find_start(&grid);
-    let mut direction = find_connected(&grid, &start);
+    fn part1(input: &Grid<u8>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Grid<u8>) -> Wide<i32> {
+        part2(input)
+    }
+}
+
+/*
+the grid, which must have an S with two pipes leading into it for the loop to start from
+ */
+pub fn parse_maze(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse(input)?;
+    let Some(start) = grid.find(|&c| c == b'S') else {
+        return Err(ParseError::at(input, &input[input.len()..], "start tile"));
+    };
+    if connections(&grid, &start).count() < 2 {
+        let row = input.lines().nth(start.row).unwrap();
+        return Err(ParseError::at(input, &row[start.col..], "start tile with two connecting pipes"));
+    }
+    Ok(grid)
+}
+
+pub fn part1(grid: &Grid<u8>) -> usize {
+    let start = find_start(grid);
+    let mut direction = find_connected(grid, &start);
 
     let mut pos = start;
     let mut steps = 0;
     loop {
-        pos = pos.dir(&direction).unwrap();
+        pos = grid.step(&pos, direction).unwrap();
         steps += 1;
 
-        let next_tile = at(&grid, &pos).unwrap();
+        let next_tile = grid[pos];
         if next_tile == b'S' {
             break;
         }
@@ -40,130 +80,83 @@ fn travelling(towards: &Direction, tile: u8) -> Direction {
     }
 }
 
-type Grid = Vec<Vec<u8>>;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Pos {
-    row: usize,
-    col: usize,
-}
-
-impl Pos {
-    fn north(&self) -> Pos {
-        Pos { row: self.row - 1, col: self.col }
-    }
-    fn east(&self) -> Pos {
-        Pos { row: self.row, col: self.col + 1 }
-    }
-
-    fn south(&self) -> Pos {
-        Pos { row: self.row + 1, col: self.col }
-    }
-
-    fn west(&self) -> Pos {
-        Pos { row: self.row, col: self.col - 1 }
-    }
-
-    fn dir(&self, dir: &Direction) -> Option<Pos> {
-        match dir {
-            North => if self.row > 0 { Some(self.north()) } else { None },
-            East => Some(self.east()),
-            South => Some(self.south()),
-            West => if self.col > 0 { Some(self.west()) } else { None },
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-fn as_bytes(input: &str) -> Grid {
-    input.lines()
-        .map(|s| s.as_bytes().to_vec())
-        .collect::<Vec<_>>()
+fn find_start(grid: &Grid<u8>) -> Pos {
+    grid.find(|&c| c == b'S').unwrap()
 }
 
-fn at(grid: &Grid, pos: &Pos) -> Option<u8> {
-    if pos.row < grid.len() && pos.col < grid[pos.row].len() {
-        Some(grid[pos.row][pos.col])
-    } else {
-        None
-    }
+fn find_connected(grid: &Grid<u8>, pos: &Pos) -> Direction {
+    connections(grid, pos).next().unwrap()
 }
 
-fn find_start(arr: &Grid) -> Pos {
-    arr.iter().enumerate()
-        .filter_map(|(row, line)|
-            line.iter()
-                .position(|&c| c == b'S')
-                .map(|col| Pos { row, col })
-        ).collect::<Vec<_>>()[0]
-}
-
-fn find_connected(grid: &Grid, pos: &Pos) -> Direction {
+/*
+the directions from pos with a pipe leading back into it
+ */
+fn connections<'a>(grid: &'a Grid<u8>, pos: &'a Pos) -> impl Iterator<Item=Direction> + 'a {
     [
         (North, [b'7', b'F', b'|']),
         (East, [b'J', b'7', b'-']),
         (South, [b'J', b'L', b'|']),
         (West, [b'L', b'F', b'-']),
     ].into_iter()
-        .find(|(dir, targets)| {
-            pos.dir(dir).map(|cell| {
-                at(grid, &cell).filter(|c| targets.contains(c)).is_some()
-            }).unwrap_or(false)
-        }).unwrap().0
+        .filter(|(dir, targets)| {
+            grid.step(pos, *dir)
+                .map(|cell| targets.contains(&grid[cell]))
+                .unwrap_or(false)
+        })
+        .map(|(dir, _)| dir)
 }
 
 const EMPTY: u8 = b'.';
 
-pub fn part2(input: &str) -> i32 {
-    let mut grid = as_bytes(input);
+fn connecting(a: Direction, b: Direction) -> u8 {
+    match (a, b) {
+        (North, South) | (South, North) => b'|',
+        (East, West) | (West, East) => b'-',
+        (North, East) | (East, North) => b'L',
+        (North, West) | (West, North) => b'J',
+        (South, West) | (West, South) => b'7',
+        (South, East) | (East, South) => b'F',
+        _ => unreachable!("no pipe turns back from {:?} to {:?}", a, b)
+    }
+}
+
+pub fn part2(grid: &Grid<u8>) -> Wide<i32> {
+    let mut grid = grid.clone();
     let start = find_start(&grid);
-    let mut direction = find_connected(&grid, &start);
+    let leaving = find_connected(&grid, &start);
+    let mut direction = leaving;
 
     let mut loop_pos: HashSet<Pos> = HashSet::new();
 
     let mut pos = start;
     loop {
-        pos = pos.dir(&direction).unwrap();
+        pos = grid.step(&pos, direction).unwrap();
 
-        let next_tile = at(&grid, &pos).unwrap();
+        let next_tile = grid[pos];
         loop_pos.insert(pos);
         if next_tile == b'S' {
             break;
         }
         direction = travelling(&direction, next_tile);
     }
-    for row in 0..grid.len() {
-        for col in 0..grid[row].len() {
-            let pos = Pos {row, col};
-            if !loop_pos.contains(&pos) {
-                mark_pos(&mut grid, &pos, EMPTY);
-            }
+    for pos in grid.positions().collect::<Vec<_>>() {
+        if !loop_pos.contains(&pos) {
+            grid[pos] = EMPTY;
         }
     }
+    // S hides a pipe the row scan has to see, the one joining the way back in to the way out
+    grid[start] = connecting(direction.opposite(), leaving);
 
-    // print the grid
-    grid.iter().for_each(|line| {
-        println!("{}", line.iter().map(|&c| c as char).collect::<String>());
-    });
-
-    grid.iter().map(|line| {
-        parsing::parse_line(line) as i32
-    }).sum()
-}
+    debug!("grid with only the loop:\n{}", grid);
 
-fn mark_pos(grid: &mut Grid, pos: &Pos, target: u8) {
-    grid.deref_mut()[pos.row].deref_mut()[pos.col] = target;
+    arith::sum("enclosed tiles", grid.rows().map(|line| {
+        parsing::parse_line(line) as Wide<i32>
+    }))
 }
 
 mod parsing {
     use std::str::from_utf8;
+    use common::arith::{self, Wide};
     use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::IResult;
@@ -180,7 +173,7 @@ mod parsing {
         count: usize,
     }
 
-    pub fn parse_line(line: &[u8]) -> u8 {
+    pub fn parse_line(line: &[u8]) -> Wide<u8> {
         fn south_to_north(input: &str) -> IResult<&str, Section> {
             separated_pair(tag("F"), many0(tag("-")), tag("J"))(input)
                 .map(|(remain, _)| (remain, Section::Wall))
@@ -219,12 +212,13 @@ mod parsing {
                 }
                 acc
             }
-        )(from_utf8(line).unwrap()).unwrap().1.count as u8
+        )(from_utf8(line).unwrap()).map(|(_, state)| arith::from_count("enclosed tiles in a row", state.count)).unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
     const TEST_INPUT: &str = "\
@@ -250,17 +244,122 @@ L7JLJL-JLJLJL--JLJ.L
 
     #[test]
     fn test_find_first() {
-        let arr = as_bytes(TEST_INPUT);
+        let arr = Grid::parse(TEST_INPUT).unwrap();
         assert_eq!(find_start(&arr), Pos { row: 2, col: 0 })
     }
 
+    #[test]
+    fn test_parse_error() {
+        assert!(parse_maze(TEST_INPUT_2).is_ok());
+        let e = parse_maze("...\n...\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (3, 1, "start tile"));
+        let e = parse_maze(".|.\n.S.\n.-.\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 2, "start tile with two connecting pipes"));
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 8);
+        assert_eq!(part1(&Grid::parse(TEST_INPUT).unwrap()), 8);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_2), 10);
+        assert_eq!(part2(&Grid::parse(TEST_INPUT_2).unwrap()), 10);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_part2_start_crossed() {
+        // S is the '|' the scan of the middle row crosses to get inside the loop
+        let vertical = ".....\n.F-7.\n.S.|.\n.L-J.\n.....\n";
+        assert_eq!(part2(&Grid::parse(vertical).unwrap()), 1);
+        // and here an 'F' the scan crosses with the '7' after it
+        let corner = "......\n.S--7.\n.|..|.\n.L--J.\n......\n";
+        assert_eq!(part2(&Grid::parse(corner).unwrap()), 2);
+    }
+
+    /*
+    a loop around a histogram: up the first column, along the tops of the bars, down the last column and back
+    along the bottom row. returns the loop as (row, col) cells in walking order
+     */
+    fn histogram_loop() -> impl Strategy<Value = Vec<(i32, i32)>> {
+        (1..8_i32).prop_flat_map(|bottom| prop::collection::vec(0..bottom, 1..10)).prop_map(|tops| {
+            let bottom = *tops.iter().max().unwrap() + 1;
+            let mut corners = vec![(bottom, 0)];
+            for (col, &top) in tops.iter().enumerate() {
+                corners.push((top, col as i32));
+                corners.push((top, col as i32 + 1));
+            }
+            corners.push((bottom, tops.len() as i32));
+            let mut cells = vec![(bottom, 0)];
+            for &(row, col) in corners.iter().skip(1).chain([&corners[0]]) {
+                while let Some(&(at_row, at_col)) = cells.last().filter(|&&cell| cell != (row, col)) {
+                    cells.push((at_row + (row - at_row).signum(), at_col + (col - at_col).signum()));
+                }
+            }
+            cells.pop();
+            cells
+        })
+    }
+
+    /*
+    the loop placed in a grid with a margin, S at some point of the loop and junk pipes everywhere else,
+    except next to S where they could look connected
+     */
+    fn maze() -> impl Strategy<Value = (Vec<(i32, i32)>, String)> {
+        let junk = prop::collection::vec(prop::sample::select(b".|-LJ7F".to_vec()), 20 * 20);
+        (histogram_loop(), 0..3_i32, 0..3_i32, any::<prop::sample::Index>(), junk)
+            .prop_map(|(cells, top, left, start, junk)| {
+                let cells = cells.into_iter().map(|(row, col)| (row + top, col + left)).collect::<Vec<_>>();
+                let start = start.index(cells.len());
+                let (height, width) = (cells.iter().map(|cell| cell.0).max().unwrap() + 2, cells.iter().map(|cell| cell.1).max().unwrap() + 2);
+                let mut rows = (0..height).map(|row| (0..width).map(|col| junk[(row * 20 + col) as usize]).collect::<Vec<_>>()).collect::<Vec<_>>();
+                let (start_row, start_col) = cells[start];
+                for (row, col) in [(start_row - 1, start_col), (start_row + 1, start_col), (start_row, start_col - 1), (start_row, start_col + 1)] {
+                    if row >= 0 && col >= 0 && row < height && col < width {
+                        rows[row as usize][col as usize] = b'.';
+                    }
+                }
+                for (idx, &(row, col)) in cells.iter().enumerate() {
+                    let previous = cells[(idx + cells.len() - 1) % cells.len()];
+                    let next = cells[(idx + 1) % cells.len()];
+                    rows[row as usize][col as usize] = if idx == start { b'S' } else { tile((row, col), previous, next) };
+                }
+                let text = rows.into_iter().map(|row| String::from_utf8(row).unwrap() + "\n").collect();
+                (cells, text)
+            })
+    }
+
+    fn tile(at: (i32, i32), previous: (i32, i32), next: (i32, i32)) -> u8 {
+        let towards = |(row, col): (i32, i32)| match (row - at.0, col - at.1) {
+            (-1, 0) => 'N',
+            (1, 0) => 'S',
+            (0, 1) => 'E',
+            _ => 'W',
+        };
+        match [towards(previous), towards(next)] {
+            ['N', 'S'] | ['S', 'N'] => b'|',
+            ['E', 'W'] | ['W', 'E'] => b'-',
+            ['N', 'E'] | ['E', 'N'] => b'L',
+            ['N', 'W'] | ['W', 'N'] => b'J',
+            ['S', 'W'] | ['W', 'S'] => b'7',
+            _ => b'F',
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1((cells, text) in maze()) {
+            prop_assert_eq!(part1(&Grid::parse(&text).unwrap()), cells.len() / 2);
+        }
+
+        // Pick's theorem: area = inside + boundary / 2 - 1, with the area from the shoelace formula
+        #[test]
+        fn prop_part2((cells, text) in maze()) {
+            let twice_area = cells.iter().zip(cells.iter().cycle().skip(1))
+                .map(|(&(row, col), &(next_row, next_col))| row * next_col - next_row * col)
+                .sum::<i32>().abs();
+            let expected = (twice_area - cells.len() as i32) / 2 + 1;
+            prop_assert_eq!(part2(&Grid::parse(&text).unwrap()), expected as Wide<i32>);
+        }
+    }
+}
@@ -1,7 +1,5 @@
-use day10::*;
+use day10::Day10;
 
 fn main() {
-    let input = include_str!("input.txt");
-    println!("part1: {}", part1(input));
-    println!("part2: {}", part2(input));
+    common::run::<Day10>();
 }
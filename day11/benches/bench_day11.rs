@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day11::Day11;
+
+fn bench_day11(c: &mut Criterion) {
+    common::bench::bench_solution::<Day11>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day11);
+criterion_main!(benches);
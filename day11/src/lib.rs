@@ -1,11 +1,36 @@
 use std::collections::HashSet;
+use common::{ParseError, Solution};
+use grid::{Grid, Pos};
 
-pub fn part1(input: &str) -> usize {
-    common(input, 1)
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(input: &Grid<u8>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Grid<u8>) -> usize {
+        part2(input)
+    }
 }
 
-fn common(input: &str, factor: usize) -> usize {
-    let mut galaxies = list_galaxies(input);
+pub fn part1(image: &Grid<u8>) -> usize {
+    common(image, 1)
+}
+
+fn common(image: &Grid<u8>, factor: usize) -> usize {
+    let mut galaxies = list_galaxies(image);
     // println!("before");
     // galaxies.iter().for_each(|g| println!("{:?}", g));
     expand_universe(&mut galaxies, factor);
@@ -16,30 +41,16 @@ fn common(input: &str, factor: usize) -> usize {
         let a = &galaxies[i];
         (i + 1..galaxies.len()).map(|j| {
             let b = &galaxies[j];
-            calculate_distance(a, b)
+            a.manhattan(b)
         })
     }).sum()
 }
 
-#[derive(Debug)]
-struct Pos {
-    row: usize,
-    col: usize,
+fn list_galaxies(image: &Grid<u8>) -> Vec<Pos> {
+    image.find_all(|&c| c == b'#').collect()
 }
 
-fn list_galaxies(input: &str) -> Vec<Pos> {
-    let mut galaxies = Vec::new();
-    for (row, line) in input.lines().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            if c == '#' {
-                galaxies.push(Pos { row, col });
-            }
-        }
-    }
-    galaxies
-}
-
-fn expand_universe(galaxies: &mut Vec<Pos>, factor: usize) {
+fn expand_universe(galaxies: &mut [Pos], factor: usize) {
     let row_expansion = calculate_expansion(galaxies.iter().map(|g| g.row), factor);
     let col_expansion = calculate_expansion(galaxies.iter().map(|g| g.col), factor);
 
@@ -52,28 +63,22 @@ fn expand_universe(galaxies: &mut Vec<Pos>, factor: usize) {
 fn calculate_expansion<I>(positions: I, factor: usize) -> Vec<usize> where I: Iterator<Item=usize> {
     let occupied = positions.collect::<HashSet<_>>();
     let &max = occupied.iter().max().unwrap();
-    let mut expansion = Vec::new();
-    expansion.resize(max + 1, 0);
     let mut expansion_so_far = 0;
-    for i in 1..=max {
-        if !occupied.contains(&i) {
-            expansion_so_far += 1 * factor;
+    (0..=max).map(|i| {
+        if i > 0 && !occupied.contains(&i) {
+            expansion_so_far += factor;
         }
-        expansion[i] = expansion_so_far;
-    }
-    expansion
+        expansion_so_far
+    }).collect()
 }
 
-fn calculate_distance(a: &Pos, b: &Pos) -> usize {
-    a.row.abs_diff(b.row) + a.col.abs_diff(b.col)
-}
-
-pub fn part2(input: &str) -> usize {
-    common(input, 1000000-1)
+pub fn part2(image: &Grid<u8>) -> usize {
+    common(image, 1000000-1)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
     const TEST_INPUT: &str = "\
@@ -90,12 +95,52 @@ mod tests {
 ";
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 374);
+        assert_eq!(part1(&Grid::parse(TEST_INPUT).unwrap()), 374);
     }
 
     #[test]
     fn test_common() {
-        assert_eq!(common(TEST_INPUT, 99), 8410);
-        assert_eq!(common(TEST_INPUT, 9), 1030);
+        let image = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(common(&image, 99), 8410);
+        assert_eq!(common(&image, 9), 1030);
+    }
+
+    /*
+    a sparse image with at least one galaxy
+     */
+    fn image() -> impl Strategy<Value = Vec<Vec<bool>>> {
+        (1..15_usize, 1..15_usize)
+            .prop_flat_map(|(width, height)| prop::collection::vec(prop::collection::vec(prop::bool::weighted(0.15), width), height))
+            .prop_filter("no galaxy", |rows| rows.iter().flatten().any(|&galaxy| galaxy))
+    }
+
+    fn render(rows: &[Vec<bool>]) -> String {
+        rows.iter().map(|row| row.iter().map(|&galaxy| if galaxy { '#' } else { '.' }).collect::<String>() + "\n").collect()
+    }
+
+    // every empty row or column crossed between two galaxies counts as factor of them
+    fn distances(rows: &[Vec<bool>], factor: usize) -> usize {
+        let galaxies = rows.iter().enumerate()
+            .flat_map(|(row, cells)| cells.iter().enumerate().filter(|(_, &galaxy)| galaxy).map(move |(col, _)| (row, col)))
+            .collect::<Vec<_>>();
+        let empty_row = |row: usize| !rows[row].iter().any(|&galaxy| galaxy);
+        let empty_col = |col: usize| !rows.iter().any(|cells| cells[col]);
+        let span = |a: usize, b: usize, empty: &dyn Fn(usize) -> bool| (a.min(b)..a.max(b)).map(|i| if empty(i) { factor } else { 1 }).sum::<usize>();
+        galaxies.iter().enumerate()
+            .flat_map(|(i, a)| galaxies[i + 1..].iter().map(move |b| (a, b)))
+            .map(|(&(a_row, a_col), &(b_row, b_col))| span(a_row, b_row, &empty_row) + span(a_col, b_col, &empty_col))
+            .sum()
     }
-}
\ No newline at end of file
+
+    proptest! {
+        #[test]
+        fn prop_part1(rows in image()) {
+            prop_assert_eq!(part1(&Grid::parse(&render(&rows)).unwrap()), distances(&rows, 2));
+        }
+
+        #[test]
+        fn prop_part2(rows in image()) {
+            prop_assert_eq!(part2(&Grid::parse(&render(&rows)).unwrap()), distances(&rows, 1000000));
+        }
+    }
+}
@@ -1,8 +1,5 @@
-use day11::*;
+use day11::Day11;
 
 fn main() {
-    let input = include_str!("input.txt");
-    println!("part1: {}", part1(input));
-    println!("part2: {}", part2(input));
-    println!("usize::MAX: {}", usize::MAX);
+    common::run::<Day11>();
 }
@@ -1,11 +1,14 @@
-use std::time::Duration;
 use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
 use day12::*;
 
+fn bench_day12(c: &mut Criterion) {
+    common::bench::bench_solution::<Day12>(c, include_str!("../src/input.txt"));
+}
+
 fn bench_part2_line4(c: &mut Criterion) {
     let input = include_str!("../src/input.txt");
-    let input = &input.lines().skip(3).take(1).collect::<Vec<_>>().join("\n");
+    let input = &parse_records(input).unwrap().into_iter().skip(3).take(1).collect::<Vec<_>>();
 
     c.bench_function("part2_line4", |b| b.iter(||
         part2(black_box(input))
@@ -14,9 +17,9 @@ fn bench_part2_line4(c: &mut Criterion) {
 
 fn bench_part2_20(c: &mut Criterion) {
     let input = include_str!("../src/input.txt");
-    let input = &input.lines().take(20).collect::<Vec<_>>().join("\n");
+    let input = &parse_records(input).unwrap().into_iter().take(20).collect::<Vec<_>>();
 
-    c.bench_function("part2", |b| b.iter(||
+    c.bench_function("part2_20", |b| b.iter(||
         part2(black_box(input))
     ));
 }
@@ -25,7 +28,6 @@ criterion_group!{
     name = benches;
     // This can be any expression that returns a `Criterion` object.
     config = Criterion::default().sample_size(10);
-    targets = bench_part2_line4
+    targets = bench_day12, bench_part2_line4, bench_part2_20
 }
-// criterion_group!(benches, bench_part2_line4);
 criterion_main!(benches);
\ No newline at end of file
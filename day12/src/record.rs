@@ -1,18 +1,33 @@
-use std::str::from_utf8;
+use std::str::{from_utf8, FromStr};
+use common::ParseError;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Record {
     pub slots: Vec<u8>,
     pub counts: Vec<usize>,
 }
-impl Record {
-    pub fn from_str(line: &str) -> Self {
-        let parts = line.split_ascii_whitespace().collect::<Vec<_>>();
-        Record {
-            slots: parts[0].bytes().collect(),
-            counts: parts[1].split(',').map(|s| s.parse().unwrap()).collect(),
+// example line
+// ???.### 1,1,3
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        let (slots, counts) = line.split_once(' ')
+            .ok_or_else(|| ParseError::at(line, &line[line.len()..], "\" \" followed by group sizes"))?;
+        if let Some(idx) = slots.find(|c| !matches!(c, '.' | '#' | '?')) {
+            return Err(ParseError::at(line, &slots[idx..], "'.', '#' or '?'"));
         }
+        let counts = counts.split(',')
+            .map(|count| match count.parse() {
+                Ok(count) if count > 0 => Ok(count),
+                _ => Err(ParseError::at(line, count, "group size")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Record { slots: slots.bytes().collect(), counts })
     }
+}
+
+impl Record {
 
     pub fn tail(&self) -> Record {
         Record {
@@ -48,22 +63,31 @@ impl Record {
     }
 }
 
+impl std::fmt::Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let slots = from_utf8(&self.slots).unwrap();
+        let counts = self.counts.iter().map(|&n| n.to_string()).collect::<Vec<_>>().join(",");
+        write!(f, "{} {}", slots, counts)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Record::from_str("#?. 1,2"), Ok(Record { slots: b"#?.".to_vec(), counts: vec![1, 2] }));
+        let e = Record::from_str("#x. 1,2").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (2, "'.', '#' or '?'"));
+        let e = Record::from_str("#?. 1,,2").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (7, "group size"));
+    }
+
     #[test]
     fn abc() {
-        let v = vec![1, 2, 3, 4, 5];
+        let v = [1, 2, 3, 4, 5];
         let re = v.iter().rev().skip(2).rev().skip(1).copied().collect::<Vec<_>>();
         assert_eq!(re, vec![2, 3]);
     }
 }
-
-impl std::fmt::Display for Record {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let slots = from_utf8(&self.slots).unwrap();
-        let counts = self.counts.iter().map(|&n| n.to_string()).collect::<Vec<_>>().join(",");
-        write!(f, "{} {}", slots, counts)
-    }
-}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day2::Day2;
+
+fn bench_day2(c: &mut Criterion) {
+    common::bench::bench_solution::<Day2>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day2);
+criterion_main!(benches);
@@ -0,0 +1,186 @@
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Vec<Game>;
+    type Part1 = Wide<i32>;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        parsing::parse_games(input)
+    }
+
+    fn part1(input: &Vec<Game>) -> Wide<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Game>) -> Wide<i32> {
+        part2(input)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+#[derive(Clone)]
+pub struct Game {
+    id: i32,
+    draws: Vec<Vec<(i32, Color)>>,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Color {
+    Red,
+    Blue,
+    Green,
+}
+
+pub mod parsing {
+    use super::*;
+
+    use combinators::text::{complete, label_id, lines, tag};
+    use common::parse::IResult;
+    use nom::branch::alt;
+    use nom::character::complete::i32;
+    use nom::combinator::{cut, value};
+    use nom::multi::separated_list1;
+    use nom::sequence::{pair, separated_pair};
+
+    fn parse_color(input: &str) -> IResult<&str, Color> {
+        alt((value(Color::Red, tag("red")), value(Color::Blue, tag("blue")), value(Color::Green, tag("green"))))(input)
+    }
+
+    fn parse_color_count(input: &str) -> IResult<&str, (i32, Color)> {
+        separated_pair(i32, tag(" "), parse_color)(input)
+    }
+
+    fn parse_draw(input: &str) -> IResult<&str, Vec<(i32, Color)>> {
+        separated_list1(tag(", "), cut(parse_color_count))(input)
+    }
+
+    fn parse_draws(input: &str) -> IResult<&str, Vec<Vec<(i32, Color)>>> {
+        separated_list1(tag("; "), cut(parse_draw))(input)
+    }
+
+    pub fn parse_game(input: &str) -> IResult<&str, Game> {
+        pair(label_id("Game", i32), parse_draws)(input)
+            .map(|(remaining, (id, draws))| (remaining, Game { id, draws }))
+    }
+
+    pub fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+        lines(input, |line| complete(parse_game, line))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_game() {
+            let input = "Game 1: 1 red, 3 blue, 11 green; 1 blue, 5 red; 3 blue, 5 green, 13 red; 6 red, 1 blue, 4 green; 16 red, 12 green";
+            assert_eq!(parse_game(input), Ok(("", Game { id: 1, draws: vec![
+                vec![(1, Color::Red), (3, Color::Blue), (11, Color::Green)],
+                vec![(1, Color::Blue), (5, Color::Red)],
+                vec![(3, Color::Blue), (5, Color::Green), (13, Color::Red)],
+                vec![(6, Color::Red), (1, Color::Blue), (4, Color::Green)],
+                vec![(16, Color::Red), (12, Color::Green)],
+            ] })));
+        }
+
+        #[test]
+        fn test_parse_games_error() {
+            let e = parse_games("Game 1: 3 blue\nGame 2: 3 blue; 4 purple").unwrap_err();
+            assert_eq!((e.line, e.column, e.expected.as_str()), (2, 19, "\"red\", \"blue\" or \"green\""));
+        }
+    }
+}
+
+pub fn part1(games: &[Game]) -> Wide<i32> {
+    // maximum 12 red cubes, 13 green cubes, and 14 blue cubes
+    arith::sum("possible game ids", games.iter().filter_map(|game| {
+        let red = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Red { Some(count) } else { None }).max().unwrap_or(0);
+        let green = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Green { Some(count) } else { None }).max().unwrap_or(0);
+        let blue = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Blue { Some(count) } else { None }).max().unwrap_or(0);
+        if red <= 12 && green <= 13 && blue <= 14 {
+            Some(game.id as Wide<i32>)
+        } else {
+            None
+        }
+    }))
+}
+
+pub fn part2(games: &[Game]) -> Wide<i32> {
+    arith::sum("cube set powers", games.iter().map(|game| {
+        let red = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Red { Some(count) } else { None }).max().unwrap_or(0);
+        let green = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Green { Some(count) } else { None }).max().unwrap_or(0);
+        let blue = game.draws.iter().flatten().filter_map(|&(count, color)| if color == Color::Blue { Some(count) } else { None }).max().unwrap_or(0);
+        let power = arith::mul("cube set power", red as Wide<i32>, green as Wide<i32>);
+        arith::mul("cube set power", power, blue as Wide<i32>)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    const COLORS: [(Color, &str); 3] = [(Color::Red, "red"), (Color::Green, "green"), (Color::Blue, "blue")];
+
+    /*
+    games numbered from 1, each draw shows every colour at most once
+     */
+    fn games() -> impl Strategy<Value = Vec<Game>> {
+        let draw = prop::sample::subsequence(COLORS.to_vec(), 1..=3)
+            .prop_shuffle()
+            .prop_flat_map(|colors| colors.into_iter()
+                .map(|(color, _)| (1..=20).prop_map(move |count| (count, color)))
+                .collect::<Vec<_>>());
+        prop::collection::vec(prop::collection::vec(draw, 1..6), 1..20)
+            .prop_map(|games| games.into_iter().enumerate()
+                .map(|(idx, draws)| Game { id: idx as i32 + 1, draws })
+                .collect())
+    }
+
+    fn render(games: &[Game]) -> String {
+        games.iter().map(|game| {
+            let draws = game.draws.iter().map(|draw| {
+                draw.iter()
+                    .map(|&(count, color)| format!("{} {}", count, COLORS.iter().find(|(c, _)| *c == color).unwrap().1))
+                    .collect::<Vec<_>>().join(", ")
+            }).collect::<Vec<_>>().join("; ");
+            format!("Game {}: {}\n", game.id, draws)
+        }).collect()
+    }
+
+    fn fewest(game: &Game, color: Color) -> i32 {
+        game.draws.iter().flatten().fold(0, |most, &(count, c)| if c == color { most.max(count) } else { most })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_round_trip(games in games()) {
+            prop_assert_eq!(parsing::parse_games(&render(&games)), Ok(games));
+        }
+
+        #[test]
+        fn prop_part1(games in games()) {
+            let limit = |color| match color { Color::Red => 12, Color::Green => 13, Color::Blue => 14 };
+            let expected = games.iter()
+                .filter(|game| game.draws.iter().flatten().all(|&(count, color)| count <= limit(color)))
+                .map(|game| game.id)
+                .sum::<i32>();
+            prop_assert_eq!(part1(&parsing::parse_games(&render(&games)).unwrap()), expected as Wide<i32>);
+        }
+
+        #[test]
+        fn prop_part2(games in games()) {
+            let expected = games.iter()
+                .map(|game| fewest(game, Color::Red) * fewest(game, Color::Green) * fewest(game, Color::Blue))
+                .sum::<i32>();
+            prop_assert_eq!(part2(&parsing::parse_games(&render(&games)).unwrap()), expected as Wide<i32>);
+        }
+    }
+}
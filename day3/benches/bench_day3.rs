@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day3::Day3;
+
+fn bench_day3(c: &mut Criterion) {
+    common::bench::bench_solution::<Day3>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day3);
+criterion_main!(benches);
@@ -0,0 +1,209 @@
+use std::ops::Range;
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
+use grid::{Grid, Pos};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Grid<u8>;
+    type Part1 = Wide<i32>;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(input: &Grid<u8>) -> Wide<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &Grid<u8>) -> Wide<i32> {
+        part2(input)
+    }
+}
+
+pub fn part1(schematic: &Grid<u8>) -> Wide<i32> {
+    arith::sum("part numbers", (0..schematic.height()).flat_map(|row| {
+        number_with_range(schematic.row(row)).into_iter()
+            .filter(move |(_, range)| neighbours_of_range(schematic, row, range, |c| !c.is_ascii_digit() && c != b'.'))
+            .map(|(number, _)| number)
+    }))
+}
+
+fn number_with_range(input: &[u8]) -> Vec<(Wide<i32>, Range<usize>)> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut number = 0;
+    let mut in_number = false;
+    for (i, &c) in input.iter().enumerate() {
+        if c.is_ascii_digit() {
+            if !in_number {
+                start = i;
+                in_number = true;
+            }
+            number = arith::add("part number", arith::mul("part number", number, 10), (c - b'0') as Wide<i32>);
+        } else if in_number {
+            in_number = false;
+            result.push((number, start..i));
+            number = 0;
+        }
+    }
+    if in_number {
+        result.push((number, start..input.len()));
+    }
+    result
+}
+
+fn neighbours_of_range<F>(schematic: &Grid<u8>, row: usize, range: &Range<usize>, predicate: F) -> bool
+    where F: Fn(u8) -> bool {
+    range.clone()
+        .flat_map(|col| schematic.neighbours8(&Pos::new(row, col)))
+        .any(|pos| predicate(schematic[pos]))
+}
+
+pub fn part2(schematic: &Grid<u8>) -> Wide<i32> {
+    let numbers: Vec<Vec<(Wide<i32>, Range<usize>)>> = schematic.rows().map(number_with_range).collect();
+    arith::sum("gear ratios", schematic.find_all(|&c| c == b'*')
+        .filter_map(|pos| find_gear_ratio(schematic, &pos, &numbers)))
+}
+
+fn find_gear_ratio(schematic: &Grid<u8>, pos: &Pos, numbers: &[Vec<(Wide<i32>, Range<usize>)>]) -> Option<Wide<i32>> {
+    let mut part_numbers: Vec<(usize, usize)> = schematic.neighbours8(pos)
+        .filter_map(|neighbour| {
+            numbers[neighbour.row].iter()
+                .position(|(_, range)| range.contains(&neighbour.col))
+                .map(|idx| (neighbour.row, idx))
+        })
+        .collect();
+    // a number touching the gear with several digits is still one number
+    part_numbers.sort();
+    part_numbers.dedup();
+
+    if let [(row_a, idx_a), (row_b, idx_b)] = part_numbers[..] {
+        Some(arith::mul("gear ratio", numbers[row_a][idx_a].0, numbers[row_b][idx_b].0))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input =
+"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        assert_eq!(part1(&Grid::parse(input).unwrap()), 4361);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input =
+"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        assert_eq!(part2(&Grid::parse(input).unwrap()), 467835);
+    }
+
+    #[test]
+    fn test_gear_on_the_border() {
+        let input =
+"*12
+3..";
+        assert_eq!(part1(&Grid::parse(input).unwrap()), 15);
+        assert_eq!(part2(&Grid::parse(input).unwrap()), 36);
+    }
+
+    /*
+    rows of numbers, dots and symbols; a number is always followed by a non-digit so values stay small
+     */
+    fn schematic() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        let token = prop_oneof![
+            3 => "[1-9][0-9]{0,2}[.*#+$/=%@&-]",
+            4 => Just(".".to_string()),
+            1 => "[*#+$/=%@&-]",
+        ];
+        (1..20_usize, 1..15_usize).prop_flat_map(move |(width, height)| {
+            prop::collection::vec(prop::collection::vec(token.clone(), width), height)
+                .prop_map(move |rows| rows.into_iter().map(|tokens| tokens.concat().as_bytes()[..width].to_vec()).collect())
+        })
+    }
+
+    fn render(rows: &[Vec<u8>]) -> String {
+        rows.iter().map(|row| String::from_utf8(row.clone()).unwrap() + "\n").collect()
+    }
+
+    // (row, first column, column after the last digit, value) of every number
+    fn numbers(rows: &[Vec<u8>]) -> Vec<(usize, usize, usize, Wide<i32>)> {
+        let mut numbers = vec![];
+        for (row, line) in rows.iter().enumerate() {
+            let mut col = 0;
+            while col < line.len() {
+                if line[col].is_ascii_digit() {
+                    let end = (col..line.len()).find(|&c| !line[c].is_ascii_digit()).unwrap_or(line.len());
+                    numbers.push((row, col, end, std::str::from_utf8(&line[col..end]).unwrap().parse().unwrap()));
+                    col = end;
+                } else {
+                    col += 1;
+                }
+            }
+        }
+        numbers
+    }
+
+    fn touches(&(number_row, start, end, _): &(usize, usize, usize, Wide<i32>), row: usize, col: usize) -> bool {
+        row + 1 >= number_row && row <= number_row + 1 && col + 1 >= start && col <= end
+    }
+
+    fn cells(rows: &[Vec<u8>]) -> impl Iterator<Item = (usize, usize, u8)> + '_ {
+        rows.iter().enumerate().flat_map(|(row, line)| line.iter().enumerate().map(move |(col, &c)| (row, col, c)))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(rows in schematic()) {
+            let expected = numbers(&rows).iter()
+                .filter(|number| cells(&rows).any(|(row, col, c)| !c.is_ascii_digit() && c != b'.' && touches(number, row, col)))
+                .map(|number| number.3)
+                .sum::<Wide<i32>>();
+            prop_assert_eq!(part1(&Grid::parse(&render(&rows)).unwrap()), expected);
+        }
+
+        #[test]
+        fn prop_part2(rows in schematic()) {
+            let numbers = numbers(&rows);
+            let expected = cells(&rows)
+                .filter(|&(_, _, c)| c == b'*')
+                .filter_map(|(row, col, _)| {
+                    match numbers.iter().filter(|number| touches(number, row, col)).collect::<Vec<_>>()[..] {
+                        [a, b] => Some(a.3 * b.3),
+                        _ => None,
+                    }
+                })
+                .sum::<Wide<i32>>();
+            prop_assert_eq!(part2(&Grid::parse(&render(&rows)).unwrap()), expected);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day4::Day4;
+
+fn bench_day4(c: &mut Criterion) {
+    common::bench::bench_solution::<Day4>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day4);
+criterion_main!(benches);
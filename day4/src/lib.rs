@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Vec<Card>;
+    type Part1 = Wide<i32>;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+        parsing::parse_cards(input)
+    }
+
+    fn part1(input: &Vec<Card>) -> Wide<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Card>) -> Wide<i32> {
+        part2(input)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    id: i32,
+    winning: HashSet<i32>,
+    present: Vec<i32>,
+}
+
+impl Card {
+    fn match_count(&self) -> usize {
+        let present = self.present.iter().cloned().collect::<HashSet<_>>();
+        self.winning.intersection(&present).count()
+    }
+}
+
+pub mod parsing {
+    use combinators::text::{complete, label_id, lines, numbers, tag};
+    use common::parse::IResult;
+    use nom::character::complete::{i32, space1};
+    use nom::sequence::{delimited, preceded, tuple};
+    use super::*;
+
+    // example line
+    // Card   1: 82 41 56 54 18 62 29 55 34 20 | 37 14 10 80 58 11 65 96 90  8 59 32 53 21 98 83 17  9 87 25 71 77 70 73 24
+
+    pub fn parse_card(input: &str) -> IResult<&str, Card> {
+        tuple((label_id("Card", i32), numbers(i32), preceded(delimited(space1, tag("|"), space1), numbers(i32))))(input)
+            .map(|(remaining, (id, winning, present))| (remaining, Card { id, winning: winning.into_iter().collect(), present }))
+    }
+
+    pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+        lines(input, |line| complete(parse_card, line))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_card() {
+            let input = "Card   1: 82 41 56 54 18 62 29 55 34 20 | 37 14 10 80 58 11 65 96 90  8 59 32 53 21 98 83 17  9 87 25 71 77 70 73 24";
+            assert_eq!(parse_card(input), Ok(("", Card {
+                id: 1,
+                winning: vec![82, 41, 56, 54, 18, 62, 29, 55, 34, 20].into_iter().collect(),
+                present: vec![37, 14, 10, 80, 58, 11, 65, 96, 90, 8, 59, 32, 53, 21, 98, 83, 17, 9, 87, 25, 71, 77, 70, 73, 24]
+            })));
+        }
+    }
+}
+
+pub fn part1(input: &[Card]) -> Wide<i32> {
+    arith::sum("card points", input.iter().map(|card| {
+        let matched = card.match_count();
+        if matched > 0 {
+            arith::pow::<Wide<i32>>("card points", 2, (matched - 1) as u32)
+        } else { 0 }
+    }))
+}
+
+// Vec<Card> -> Vec<Range<usize>> -> fold((1, Vec::new<Range<usize>>())) to Vec<(i32, Range<usize>)>
+
+pub fn part2(input: &[Card]) -> Wide<i32> {
+    let mut card_copies: Vec<Wide<i32>> = input.iter().map(|_| 1).collect();
+    let ranges: Vec<_> = input.iter().enumerate()
+        .map(|(idx, card)| (idx+1)..(idx+card.match_count()+1))
+        .collect();
+    ranges.iter().enumerate()
+        .for_each(|(idx, r)| {
+            let copies = card_copies[idx];
+            card_copies[r.to_owned()].iter_mut().for_each(|c| *c = arith::add("card copies", *c, copies))
+        });
+    arith::sum("card copies", card_copies)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_part2() {
+        let input =
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+        let result = part2(&parsing::parse_cards(input).unwrap());
+        assert_eq!(result, 30);
+    }
+
+    /*
+    (winning numbers, numbers you have, how many of them win) per card,
+    never winning copies of cards past the end of the table
+     */
+    fn cards() -> impl Strategy<Value = Vec<(Vec<i32>, Vec<i32>, usize)>> {
+        (1..10_usize, 1..8_usize, 0..6_usize).prop_flat_map(|(count, winning, extra)| {
+            (0..count).map(|idx| {
+                let present = winning + extra;
+                (prop::sample::subsequence((1..100).collect::<Vec<_>>(), winning + present).prop_shuffle(), 0..=winning.min(count - idx - 1))
+                    .prop_map(move |(numbers, matches)| {
+                        let (winning_numbers, others) = numbers.split_at(winning);
+                        let mut present_numbers = winning_numbers[..matches].to_vec();
+                        present_numbers.extend(&others[..present - matches]);
+                        (winning_numbers.to_vec(), present_numbers, matches)
+                    })
+            }).collect::<Vec<_>>()
+        })
+    }
+
+    fn render(cards: &[(Vec<i32>, Vec<i32>, usize)]) -> String {
+        let numbers = |numbers: &[i32]| numbers.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+        cards.iter().enumerate()
+            .map(|(idx, (winning, present, _))| format!("Card {:>3}: {} | {}\n", idx + 1, numbers(winning), numbers(present)))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(cards in cards()) {
+            let expected = cards.iter().map(|&(_, _, matches)| if matches > 0 { 1 << (matches - 1) } else { 0 }).sum::<i32>();
+            prop_assert_eq!(part1(&parsing::parse_cards(&render(&cards)).unwrap()), expected as Wide<i32>);
+        }
+
+        #[test]
+        fn prop_part2(cards in cards()) {
+            // scratch every copy one by one
+            let mut pile = (0..cards.len()).collect::<Vec<_>>();
+            let mut scratched = 0;
+            while let Some(idx) = pile.pop() {
+                scratched += 1;
+                pile.extend(idx + 1..=idx + cards[idx].2);
+            }
+            prop_assert_eq!(part2(&parsing::parse_cards(&render(&cards)).unwrap()), scratched);
+        }
+    }
+}
@@ -2,6 +2,10 @@ use criterion::{black_box, Criterion, criterion_group, criterion_main};
 
 use day5::*;
 
+fn bench_day5(c: &mut Criterion) {
+    common::bench::bench_solution::<Day5>(c, include_str!("../src/input.txt"));
+}
+
 fn bench_part2_small(c: &mut Criterion) {
     let input = include_str!("../src/testcase1.txt");
     let almanac = parsing::parse_almanac(input).unwrap().1;
@@ -20,5 +24,5 @@ fn bench_part2_limited(c: &mut Criterion) {
     ));
 }
 
-criterion_group!(benches, bench_part2_small, bench_part2_limited);
+criterion_group!(benches, bench_day5, bench_part2_small, bench_part2_limited);
 criterion_main!(benches);
\ No newline at end of file
@@ -1,4 +1,28 @@
 use std::cmp::{max, min};
+use common::{ParseError, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Almanac;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        parsing::parse_file(input)
+    }
+
+    fn part1(input: &Almanac) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Almanac) -> i64 {
+        part2_full(input)
+    }
+}
 
 pub fn part1(almanac: &Almanac) -> i64 {
     almanac
@@ -12,8 +36,10 @@ pub fn part2_full(almanac: &Almanac) -> i64 {
 }
 
 pub fn part2(almanac: &Almanac, _limit: i64) -> i64 {
-    fn combine_all(mappings: &Vec<Mapping>) -> Mapping {
-        mappings.iter().skip(1).fold(mappings[0].clone(), |acc, mapping| acc.combine(mapping))
+    // starting from the identity makes the combined sections cover every value, even for a single mapping
+    fn combine_all(mappings: &[Mapping]) -> Mapping {
+        let identity = Mapping { from: mappings[0].from.clone(), to: mappings[0].from.clone(), sections: vec![] };
+        mappings.iter().fold(identity, |acc, mapping| acc.combine(mapping))
     }
 
     let seed_ranges = almanac.seeds
@@ -21,10 +47,10 @@ pub fn part2(almanac: &Almanac, _limit: i64) -> i64 {
         .map(|window| (window[0], window[1]))
         .collect::<Vec<_>>();
 
-    let mut combined = combine_all(&almanac.mappings);
-    combined.sections.sort_by_key(|section| section.destination_start);
-    combined.sections.iter().filter_map(|section| {
-        seed_ranges.iter().find_map(|&(start, count)| {
+    let combined = combine_all(&almanac.mappings);
+    // a section maps increasingly, so the lowest location of each overlap is at its start
+    combined.sections.iter().flat_map(|section| {
+        seed_ranges.iter().filter_map(|&(start, count)| {
             if section.source_start < start + count && section.source_start + section.size > start {
                 let intersection_start = max(section.source_start, start);
                 Some(section.apply(intersection_start))
@@ -37,6 +63,7 @@ pub fn part2(almanac: &Almanac, _limit: i64) -> i64 {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
     #[test]
@@ -76,13 +103,27 @@ mod tests {
         assert_eq!(almanac.chain_lookup("seed", 13, "location"), 35);
     }
 
+    #[test]
+    fn test_part2_unmapped_seeds() {
+        // no section covers the seeds, so they keep their numbers all the way to location
+        let almanac = parsing::parse_file("seeds: 5 2\n\nseed-to-location map:\n50 0 3\n").unwrap();
+        assert_eq!(part2_full(&almanac), 5);
+    }
+
+    #[test]
+    fn test_part2_every_seed_range() {
+        // the section overlaps both seed ranges, the second one gives the lowest location
+        let almanac = parsing::parse_file("seeds: 10 5 0 5\n\nseed-to-location map:\n100 0 20\n").unwrap();
+        assert_eq!(part2_full(&almanac), 100);
+    }
+
     #[test]
     fn test_window() {
         let arr = [1, 2, 3, 4];
         assert_eq!(arr.windows(2).step_by(2).collect::<Vec<_>>(), vec![&[1, 2], &[3, 4]]);
     }
 
-    fn assert_equivalent(mapping: &Mapping, mappings: &Vec<Mapping>, from: &str, to: &str) {
+    fn assert_equivalent(mapping: &Mapping, mappings: &[Mapping], from: &str, to: &str) {
         (0..100).for_each(|idx|
             assert_eq!(mapping.apply(idx), Almanac::chain_lookup_with(mappings, from, idx, to), "idx={}", idx)
         );
@@ -104,7 +145,7 @@ mod tests {
             sections: vec![]
         };
 
-        assert_equivalent(&a_to_b.combine(&identity), &vec![a_to_b, identity], "A", "C");
+        assert_equivalent(&a_to_b.combine(&identity), &[a_to_b, identity], "A", "C");
     }
 
     #[test]
@@ -138,6 +179,67 @@ mod tests {
 
         assert_equivalent(&combined, &almanac.mappings, "seed", "fertilizer")
     }
+
+    const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+    /*
+    a mapping with non-overlapping source ranges below 120
+     */
+    fn mapping(from: &'static str, to: &'static str) -> impl Strategy<Value = Mapping> {
+        (1..5_usize).prop_flat_map(|count| prop::sample::subsequence((0..120).collect::<Vec<i64>>(), count * 2))
+            .prop_flat_map(|bounds| bounds.chunks(2)
+                .map(|range| (0..150_i64).prop_map({
+                    let range = range.to_vec();
+                    move |destination_start| Section { destination_start, source_start: range[0], size: range[1] - range[0] }
+                }))
+                .collect::<Vec<_>>()
+                .prop_shuffle())
+            .prop_map(move |sections| Mapping { from: from.to_string(), to: to.to_string(), sections })
+    }
+
+    /*
+    seed ranges and a chain of mappings from seed through some categories to location
+     */
+    fn almanac() -> impl Strategy<Value = Almanac> {
+        let seeds = prop::collection::vec((0..120_i64, 1..20_i64), 1..4)
+            .prop_map(|ranges| ranges.into_iter().flat_map(|(start, len)| [start, len]).collect::<Vec<_>>());
+        let chain = prop::sample::subsequence(CATEGORIES[1..7].to_vec(), 0..4).prop_flat_map(|middle| {
+            let names = [&["seed"], middle.as_slice(), &["location"]].concat();
+            names.windows(2).map(|pair| mapping(pair[0], pair[1])).collect::<Vec<_>>()
+        });
+        (seeds, chain).prop_map(|(seeds, mappings)| Almanac { seeds, mappings })
+    }
+
+    fn render(almanac: &Almanac) -> String {
+        let seeds = almanac.seeds.iter().map(|seed| seed.to_string()).collect::<Vec<_>>().join(" ");
+        let mappings = almanac.mappings.iter().map(|mapping| {
+            let sections = mapping.sections.iter()
+                .map(|section| format!("{} {} {}", section.destination_start, section.source_start, section.size))
+                .collect::<Vec<_>>().join("\n");
+            format!("{}-to-{} map:\n{}\n", mapping.from, mapping.to, sections)
+        }).collect::<Vec<_>>().join("\n");
+        format!("seeds: {}\n\n{}", seeds, mappings)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_combine_matches_chain_lookup(almanac in almanac()) {
+            let almanac = parsing::parse_file(&render(&almanac)).unwrap();
+            let combined = almanac.mappings.iter().skip(1).fold(almanac.mappings[0].clone(), |acc, mapping| acc.combine(mapping));
+            for value in 0..200 {
+                prop_assert_eq!(combined.apply(value), almanac.chain_lookup("seed", value, "location"), "value={}", value);
+            }
+        }
+
+        #[test]
+        fn prop_part2(almanac in almanac()) {
+            let expected = almanac.seeds.chunks(2)
+                .flat_map(|range| range[0]..range[0] + range[1])
+                .map(|seed| almanac.chain_lookup("seed", seed, "location"))
+                .min().unwrap();
+            prop_assert_eq!(part2_full(&parsing::parse_file(&render(&almanac)).unwrap()), expected);
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -148,7 +250,7 @@ pub struct Almanac {
 
 impl Almanac {
 
-    pub fn chain_lookup_with(mappings: &Vec<Mapping>, from: &str, value: i64, to: &str) -> i64 {
+    pub fn chain_lookup_with(mappings: &[Mapping], from: &str, value: i64, to: &str) -> i64 {
         let mut current_value = value;
         let mut current_type = from;
         while current_type != to {
@@ -178,14 +280,14 @@ impl Mapping {
                 return Some(section)
             }
         }
-        return None
+        None
     }
 
     pub fn apply(&self, value: i64) -> i64 {
         if let Some(section) = self.find_section(value) {
             return section.apply(value)
         }
-        return value
+        value
     }
     pub fn combine(&self, other: &Mapping) -> Mapping {
         assert_eq!(self.to, other.from);
@@ -193,7 +295,7 @@ impl Mapping {
         let mut sections: Vec<Section> = Vec::new();
         let mut current = 0_i64;
 
-        fn find_next_start(sections: &Vec<Section>, value: i64) -> i64 {
+        fn find_next_start(sections: &[Section], value: i64) -> i64 {
             sections.iter()
                 .find(|section| section.source_start >= value)
                 .map(|section| section.source_start)
@@ -220,7 +322,7 @@ impl Mapping {
         Mapping {
             from: self.from.clone(),
             to: other.to.clone(),
-            sections: sections
+            sections
         }
     }
 }
@@ -244,19 +346,19 @@ impl Section {
 
 pub mod parsing {
     use super::*;
-    use nom::bytes::complete::tag;
-    use nom::character::complete::{alpha1, space1, i64};
-    use nom::IResult;
-    use nom::multi::separated_list1;
-    use nom::sequence::{preceded, separated_pair, terminated};
+    use combinators::text::{blank_line, blocks, complete, lines_of, numbers, tag};
+    use common::parse::IResult;
+    use nom::character::complete::{alpha1, line_ending, space1, i64};
+    use nom::combinator::cut;
+    use nom::sequence::{preceded, separated_pair, terminated, tuple};
 
     fn parse_seeds(input: &str) -> IResult<&str, Vec<i64>> {
-        preceded(tag("seeds: "), separated_list1(tag(" "), i64))(input)
+        preceded(tag("seeds: "), numbers(i64))(input)
     }
 
     fn parse_section(input: &str) -> IResult<&str, Section> {
-        separated_list1(space1, i64)(input)
-            .map(|(remaining, section)| (remaining, Section { destination_start: section[0], source_start: section[1], size: section[2] }))
+        tuple((i64, cut(preceded(space1, i64)), cut(preceded(space1, i64))))(input)
+            .map(|(remaining, section)| (remaining, Section { destination_start: section.0, source_start: section.1, size: section.2 }))
     }
 
     fn parse_map_heading(input: &str) -> IResult<&str, (&str, &str)> {
@@ -264,22 +366,22 @@ pub mod parsing {
     }
 
     fn parse_mapping(input: &str) -> IResult<&str, Mapping> {
-        separated_pair(parse_map_heading, tag("\n"), separated_list1(tag("\n"), parse_section))(input)
+        separated_pair(parse_map_heading, line_ending, lines_of(parse_section))(input)
             .map(|(remaining, (heading, sections))| (remaining, Mapping { from: heading.0.to_string(), to: heading.1.to_string(), sections }))
     }
 
-    fn parse_mappings(input: &str) -> IResult<&str, Vec<Mapping>> {
-        separated_list1(tag("\n\n"), parse_mapping)(input)
-    }
-
     pub fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
-        separated_pair(parse_seeds, tag("\n\n"), parse_mappings)(input)
+        separated_pair(parse_seeds, blank_line, blocks(parse_mapping))(input)
             .map(|(remaining, (seeds, mut mappings))| {
                 mappings.iter_mut().for_each(|mapping| mapping.sections.sort_by_key(|section| section.source_start));
                 (remaining, Almanac { seeds, mappings })
             })
     }
 
+    pub fn parse_file(input: &str) -> Result<Almanac, ParseError> {
+        complete(parse_almanac, input)
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day6::Day6;
+
+fn bench_day6(c: &mut Criterion) {
+    common::bench::bench_solution::<Day6>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day6);
+criterion_main!(benches);
@@ -1,21 +1,42 @@
-use crate::model::Race;
+use common::{ParseError, Solution};
+pub use crate::model::Race;
+use crate::parsing::RaceSheet;
 
 mod model;
+pub mod parsing;
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = RaceSheet;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<RaceSheet, ParseError> {
+        parsing::parse_sheet(input)
+    }
+
+    fn part1(input: &RaceSheet) -> i64 {
+        part1(&input.races)
+    }
+
+    fn part2(input: &RaceSheet) -> i64 {
+        part2(&input.kerned)
+    }
+}
+
+#[cfg(test)]
 const TESTCASE: [Race; 3] = [
     Race { time_limit: 7, record: 9 },
     Race { time_limit: 15, record: 40 },
     Race { time_limit: 30, record: 200 },
 ];
-pub const INPUT: [Race; 4] = [
-    Race { time_limit: 53, record: 333 },
-    Race { time_limit: 83, record: 1635 },
-    Race { time_limit: 72, record: 1289 },
-    Race { time_limit: 88, record: 1532 },
-];
 
+#[cfg(test)]
 const REAL_TESTCASE: Race = Race { time_limit: 71530, record: 940200 };
-pub const REAL_INPUT: Race = Race { time_limit: 53837288, record: 333163512891532 };
 
 pub fn part1(races: &[Race]) -> i64 {
     races.iter()
@@ -30,8 +51,14 @@ pub fn part2(race: &Race) -> i64 {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
+    const TEST_INPUT: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
     #[test]
     fn test_part1() {
         assert_eq!(part1(&TESTCASE), 288);
@@ -41,4 +68,61 @@ mod tests {
     fn test_part2() {
         assert_eq!(part2(&REAL_TESTCASE), 71503);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_part1_parsed() {
+        assert_eq!(part1(&parsing::parse_races(TEST_INPUT).unwrap()), 288);
+    }
+
+    #[test]
+    fn test_part2_parsed() {
+        assert_eq!(part2(&parsing::parse_kerned_race(TEST_INPUT).unwrap()), 71503);
+    }
+
+    /*
+    up to three short races, each with a record that can be beaten
+     */
+    fn races() -> impl Strategy<Value = Vec<Race>> {
+        let race = (2..40_i64).prop_flat_map(|time_limit| {
+            (0..(time_limit / 2) * (time_limit - time_limit / 2)).prop_map(move |record| Race { time_limit, record })
+        });
+        prop::collection::vec(race, 1..=3)
+    }
+
+    fn render(races: &[Race]) -> String {
+        let width = races.iter().map(|race| race.time_limit.max(race.record).to_string().len()).max().unwrap();
+        let column = |value: i64| format!("  {:>width$}", value);
+        format!("Time:    {}\nDistance:{}\n",
+                races.iter().map(|race| column(race.time_limit)).collect::<String>(),
+                races.iter().map(|race| column(race.record)).collect::<String>())
+    }
+
+    // the charge times beating the record lie strictly between the roots of c * (t - c) = record
+    fn ways(race: &Race) -> i64 {
+        let &Race { time_limit, record } = race;
+        let beats = |charge: i64| charge * (time_limit - charge) > record;
+        let root = ((time_limit * time_limit - 4 * record) as f64).sqrt();
+        let mut low = ((time_limit as f64 - root) / 2.0).floor() as i64;
+        let mut high = ((time_limit as f64 + root) / 2.0).ceil() as i64;
+        while beats(low) { low -= 1 }
+        while !beats(low + 1) { low += 1 }
+        while beats(high) { high += 1 }
+        while !beats(high - 1) { high -= 1 }
+        high - low - 1
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(races in races()) {
+            let expected = races.iter().map(ways).product::<i64>();
+            prop_assert_eq!(part1(&parsing::parse_races(&render(&races)).unwrap()), expected);
+        }
+
+        #[test]
+        fn prop_part2(races in races()) {
+            let kerned = |value: fn(&Race) -> i64| races.iter().map(|race| value(race).to_string()).collect::<String>().parse().unwrap();
+            let race = Race { time_limit: kerned(|race| race.time_limit), record: kerned(|race| race.record) };
+            prop_assert_eq!(part2(&parsing::parse_kerned_race(&render(&races)).unwrap()), ways(&race));
+        }
+    }
+}
@@ -1,6 +1,5 @@
-use day6::*;
+use day6::Day6;
 
 fn main() {
-    println!("part1: {}", part1(&INPUT));
-    println!("part2: {}", part2(&REAL_INPUT));
+    common::run::<Day6>();
 }
@@ -1,4 +1,5 @@
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Race {
     pub time_limit: i64,
     pub record: i64,
@@ -23,7 +24,6 @@ impl Race {
 #[cfg(test)]
 mod test {
     use crate::TESTCASE;
-    use super::*;
 
     #[test]
     fn test_ways_to_beat_record() {
@@ -0,0 +1,77 @@
+use common::ParseError;
+use crate::model::Race;
+
+// example input
+// Time:      7  15   30
+// Distance:  9  40  200
+
+/*
+the same sheet read both ways: as separate races for part 1 and as one kerned race for part 2
+ */
+pub struct RaceSheet {
+    pub races: Vec<Race>,
+    pub kerned: Race,
+}
+
+pub fn parse_sheet(input: &str) -> Result<RaceSheet, ParseError> {
+    Ok(RaceSheet { races: parse_races(input)?, kerned: parse_kerned_race(input)? })
+}
+
+fn numbers_after<'a>(input: &'a str, label: &'static str) -> Result<Vec<&'a str>, ParseError> {
+    let numbers = input.lines()
+        .find_map(|line| line.strip_prefix(label))
+        .ok_or_else(|| ParseError::at(input, input, format!("{:?} line", label)))?
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    match numbers.iter().find(|number| !number.bytes().all(|b| b.is_ascii_digit())) {
+        Some(bad) => Err(ParseError::at(input, bad, "number")),
+        None => Ok(numbers),
+    }
+}
+
+fn parse_number(input: &str, number: &str) -> Result<i64, ParseError> {
+    number.parse().map_err(|_| ParseError::at(input, number, "number"))
+}
+
+pub fn parse_races(input: &str) -> Result<Vec<Race>, ParseError> {
+    let times = numbers_after(input, "Time:")?;
+    let distances = numbers_after(input, "Distance:")?;
+    if times.len() != distances.len() {
+        let line = input.lines().find(|line| line.starts_with("Distance:")).unwrap();
+        return Err(ParseError::at(input, &line[line.len()..], format!("{} distances", times.len())));
+    }
+    times.into_iter()
+        .zip(distances)
+        .map(|(time_limit, record)| Ok(Race { time_limit: parse_number(input, time_limit)?, record: parse_number(input, record)? }))
+        .collect()
+}
+
+/*
+part 2 reads each line as a single number, ignoring the spaces between the digits
+ */
+pub fn parse_kerned_race(input: &str) -> Result<Race, ParseError> {
+    let kerned = |label| -> Result<i64, ParseError> {
+        let numbers = numbers_after(input, label)?;
+        numbers.concat().parse().map_err(|_| ParseError::at(input, numbers.first().copied().unwrap_or(input), "number"))
+    };
+    Ok(Race { time_limit: kerned("Time:")?, record: kerned("Distance:")? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_races() {
+        let races = parse_races("Time:      7  15\nDistance:  9  40\n").unwrap();
+        assert_eq!(races.iter().map(|race| (race.time_limit, race.record)).collect::<Vec<_>>(), vec![(7, 9), (15, 40)]);
+    }
+
+    #[test]
+    fn test_parse_races_error() {
+        let e = parse_races("Time:      7  15\nDistance:  9  4o\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 15, "number"));
+        let e = parse_races("Time:      7  15\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 1, "\"Distance:\" line"));
+    }
+}
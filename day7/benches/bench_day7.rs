@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day7::Day7;
+
+fn bench_day7(c: &mut Criterion) {
+    common::bench::bench_solution::<Day7>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day7);
+criterion_main!(benches);
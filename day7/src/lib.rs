@@ -1,9 +1,35 @@
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
+
 pub mod parsing;
 
-#[derive(Debug, PartialEq)]
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Vec<HandAndBid>;
+    type Part1 = Wide<i64>;
+    type Part2 = Wide<i64>;
+
+    fn parse(input: &str) -> Result<Vec<HandAndBid>, ParseError> {
+        parsing::parse_file(input)
+    }
+
+    fn part1(input: &Vec<HandAndBid>) -> Wide<i64> {
+        part1(&mut input.clone())
+    }
+
+    fn part2(input: &Vec<HandAndBid>) -> Wide<i64> {
+        part2(input)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Hand(Vec<u8>);
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct HandAndBid {
     hand: Hand,
     bid: i32,
@@ -19,12 +45,19 @@ enum HandType {
     HighCard=0,
 }
 
+const JACK: u8 = 11;
+const JOKER: u8 = 1;
+
 impl Hand {
-    fn strength(&self) -> u64 {
-        let mut power = Hand::determine_type(self) as u64;
+    fn with_jokers(&self) -> Hand {
+        Hand(self.0.iter().map(|&card| if card == JACK { JOKER } else { card }).collect())
+    }
+
+    fn strength(&self) -> Wide<u64> {
+        let mut power = Hand::determine_type(self) as Wide<u64>;
         for &card in &self.0 {
-            power *= 100;
-            power += card as u64;
+            power = arith::mul("hand strength", power, 100);
+            power = arith::add("hand strength", power, card as Wide<u64>);
         }
         power
     }
@@ -44,7 +77,7 @@ impl Hand {
             counts[card as usize] += 1;
         }
 
-        let joker_count = counts[1];
+        let joker_count = counts[JOKER as usize];
         if joker_count == 5 { return HandType::FiveOfAKind }
 
         let mut counts = counts.into_iter()
@@ -66,22 +99,23 @@ impl Hand {
     }
 }
 
-pub fn part1(input: &mut [HandAndBid]) -> i64 {
+pub fn part1(input: &mut [HandAndBid]) -> Wide<i64> {
     input.sort_by_key(|x| x.hand.strength());
-    input
+    arith::sum("winnings", input
         .iter().enumerate()
-        .map(|(i, x)| x.bid as i64 * (i+1) as i64)
-        .sum()
+        .map(|(i, x)| arith::mul("winnings", x.bid as Wide<i64>, arith::from_count("winnings", i+1))))
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.replace('J', "1");
-    let mut input = parsing::parse_file(&input);
+pub fn part2(input: &[HandAndBid]) -> Wide<i64> {
+    let mut input = input.iter()
+        .map(|x| HandAndBid { hand: x.hand.with_jokers(), bid: x.bid })
+        .collect::<Vec<_>>();
     part1(&mut input)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
     #[test]
@@ -92,7 +126,7 @@ KK677 28
 KTJJT 220
 QQQJA 483
 ";
-        let mut input = parsing::parse_file(input_text);
+        let mut input = parsing::parse_file(input_text).unwrap();
         assert_eq!(part1(&mut input), 6440);
     }
 
@@ -104,6 +138,63 @@ KK677 28
 KTJJT 220
 QQQJA 483
 ";
-        assert_eq!(part2(input_txt), 5905);
+        assert_eq!(part2(&parsing::parse_file(input_txt).unwrap()), 5905);
+    }
+
+    const LABELS: &str = "23456789TJQKA";
+
+    /*
+    distinct hands, so that no two of them tie, each with a bid
+     */
+    fn hands() -> impl Strategy<Value = Vec<(String, i32)>> {
+        let hand = prop::collection::vec(prop::sample::select(LABELS.chars().collect::<Vec<_>>()), 5)
+            .prop_map(|cards| cards.into_iter().collect::<String>());
+        prop::collection::btree_set(hand, 1..50)
+            .prop_flat_map(|hands| {
+                let bids = prop::collection::vec(1..1000_i32, hands.len());
+                (Just(hands), bids)
+            })
+            .prop_map(|(hands, bids)| hands.into_iter().zip(bids).collect::<Vec<_>>())
+            .prop_shuffle()
+    }
+
+    fn render(hands: &[(String, i32)]) -> String {
+        hands.iter().map(|(hand, bid)| format!("{} {}\n", hand, bid)).collect()
+    }
+
+    // type by the sorted label counts, stronger types compare greater
+    fn kind(hand: &str) -> Vec<usize> {
+        let mut counts = LABELS.chars().map(|label| hand.matches(label).count()).filter(|&count| count > 0).collect::<Vec<_>>();
+        counts.sort_by(|a, b| b.cmp(a));
+        counts
+    }
+
+    // a joker is best spent copying another label, all of them the same one
+    fn joker_kind(hand: &str) -> Vec<usize> {
+        LABELS.chars().map(|label| kind(&hand.replace('J', &label.to_string()))).max().unwrap()
+    }
+
+    fn winnings(hands: &[(String, i32)], key: impl Fn(&str) -> (Vec<usize>, Vec<usize>)) -> i64 {
+        let mut hands = hands.to_vec();
+        hands.sort_by_cached_key(|(hand, _)| key(hand));
+        hands.iter().enumerate().map(|(rank, (_, bid))| (rank as i64 + 1) * *bid as i64).sum()
+    }
+
+    fn card_ranks(hand: &str, order: &str) -> Vec<usize> {
+        hand.chars().map(|card| order.find(card).unwrap()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(hands in hands()) {
+            let expected = winnings(&hands, |hand| (kind(hand), card_ranks(hand, LABELS)));
+            prop_assert_eq!(part1(&mut parsing::parse_file(&render(&hands)).unwrap()), expected as Wide<i64>);
+        }
+
+        #[test]
+        fn prop_part2(hands in hands()) {
+            let expected = winnings(&hands, |hand| (joker_kind(hand), card_ranks(hand, "J23456789TQKA")));
+            prop_assert_eq!(part2(&parsing::parse_file(&render(&hands)).unwrap()), expected as Wide<i64>);
+        }
     }
 }
@@ -1,14 +1,16 @@
+use combinators::text::{complete, lines, tag};
+use common::ParseError;
+use common::parse::IResult;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
 use nom::combinator::value;
-use nom::{IResult, Parser};
-use nom::character::complete::{i32, line_ending, space1};
-use nom::multi::{many_m_n, separated_list1};
+use nom::character::complete::{i32, space1};
+use nom::error::context;
+use nom::multi::many_m_n;
 use nom::sequence::separated_pair;
 use crate::{Hand, HandAndBid};
 
 fn parse_card(input: &str) -> IResult<&str, u8> {
-    alt((
+    context("card", alt((
         value(1, tag("1")),
         value(2, tag("2")),
         value(3, tag("3")),
@@ -23,7 +25,7 @@ fn parse_card(input: &str) -> IResult<&str, u8> {
         value(12, tag("Q")),
         value(13, tag("K")),
         value(14, tag("A")),
-        ))(input)
+        )))(input)
 }
 
 fn parse_hand_and_bid(input: &str) -> IResult<&str, HandAndBid> {
@@ -31,8 +33,8 @@ fn parse_hand_and_bid(input: &str) -> IResult<&str, HandAndBid> {
         .map(|(remain, (hand, bid))| (remain, HandAndBid { hand: Hand(hand), bid }))
 }
 
-pub fn parse_file(input: &str) -> Vec<HandAndBid> {
-    separated_list1(line_ending, parse_hand_and_bid).parse(input).unwrap().1
+pub fn parse_file(input: &str) -> Result<Vec<HandAndBid>, ParseError> {
+    lines(input, |line| complete(parse_hand_and_bid, line))
 }
 
 #[cfg(test)]
@@ -50,4 +52,10 @@ mod tests {
     fn test_parse_hand_and_bid() {
         assert_eq!(parse_hand_and_bid("23456 123").unwrap().1, HandAndBid { hand: Hand(vec![2, 3, 4, 5, 6]), bid: 123 });
     }
+
+    #[test]
+    fn test_parse_file_error() {
+        let e = parse_file("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 4, "card"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day8::Day8;
+
+fn bench_day8(c: &mut Criterion) {
+    common::bench::bench_solution::<Day8>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day8);
+criterion_main!(benches);
@@ -1,7 +1,32 @@
 use std::collections::HashMap;
+use common::{ParseError, Solution};
+use log::trace;
 
 pub mod parsing;
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = (Vec<Step>, Vec<Node>);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parsing::parse_puzzle(input)
+    }
+
+    fn part1((steps, nodes): &Self::Input) -> i64 {
+        part1(steps, nodes)
+    }
+
+    fn part2((steps, nodes): &Self::Input) -> i64 {
+        part2(steps, nodes)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Step { Left, Right }
 
@@ -12,16 +37,15 @@ pub struct Node {
     pub right: String,
 }
 
-pub fn part1(input: &str) -> i64 {
-    let (steps, nodes) = parsing::parse_file(input);
+pub fn part1(steps: &[Step], nodes: &[Node]) -> i64 {
     let mut count = 0;
-    let mut current = find_node(&nodes, "AAA");
+    let mut current = find_node(nodes, "AAA");
     for step in steps.iter().cycle() {
         let next = match step {
             Step::Left => &current.left,
             Step::Right => &current.right
         };
-        current = find_node(&nodes, next);
+        current = find_node(nodes, next);
         count += 1;
         if current.label == "ZZZ" { break }
     }
@@ -32,8 +56,7 @@ fn find_node<'a>(nodes: &'a [Node], label: &str) -> &'a Node {
     nodes.iter().find(|node| node.label == label).unwrap()
 }
 
-pub fn part2(input: &str) -> i64 {
-    let (steps, nodes) = parsing::parse_file(input);
+pub fn part2(steps: &[Step], nodes: &[Node]) -> i64 {
     let Atlas { last_a, first_z, left_targets, right_targets } = Atlas::from(nodes);
 
     let end_patterns = (0..=last_a).map(|start| {
@@ -41,11 +64,15 @@ pub fn part2(input: &str) -> i64 {
     }).collect::<Vec<_>>();
 
     // X = s1 + n1*p1
+    // when every ghost hits its only Z exactly once per cycle, at the end of it, the answer is the LCM of the periods
+    if end_patterns.iter().all(|pattern| pattern.ends == [pattern.period] && pattern.start <= pattern.period) {
+        return end_patterns.iter().fold(1, |acc, pattern| lcm(acc, pattern.period as i64));
+    }
 
     let mut count = 0;
     let mut current = (0..=last_a).collect::<Vec<_>>();
     for step in steps.iter().cycle() {
-        println!("{}: {:?}", count, current);
+        trace!("{}: {:?}", count, current);
         let targets = match step {
             Step::Left => &left_targets,
             Step::Right => &right_targets
@@ -60,6 +87,14 @@ pub fn part2(input: &str) -> i64 {
     count
 }
 
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    a / gcd(a, b) * b
+}
+
 #[derive(Debug)]
 struct Atlas {
     first_z: usize,
@@ -69,8 +104,8 @@ struct Atlas {
 }
 
 impl Atlas {
-    fn from(nodes: Vec<Node>) -> Self {
-        let mut nodes = nodes;
+    fn from(nodes: &[Node]) -> Self {
+        let mut nodes = nodes.iter().collect::<Vec<_>>();
         nodes.sort_by_key(|node| node.label.chars().rev().collect::<String>());
         let last_a = nodes.iter().rposition(|node| node.label.ends_with("A")).unwrap();
         let first_z = nodes.iter().position(|node| node.label.ends_with("Z")).unwrap();
@@ -123,6 +158,7 @@ fn end_pattern<'a, I>(start_pos: usize, first_z: usize, left_targets: &[usize],
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
     #[test]
@@ -134,7 +170,8 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
-        assert_eq!(part1(input_text), 6);
+        let (steps, nodes) = parsing::parse_file(input_text).unwrap();
+        assert_eq!(part1(&steps, &nodes), 6);
     }
 
 const INPUT_TEXT_2: &str =
@@ -161,12 +198,43 @@ XXX = (XXX, XXX)";
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT_TEXT_2), 6);
+        let (steps, nodes) = parsing::parse_file(INPUT_TEXT_2).unwrap();
+        assert_eq!(part2(&steps, &nodes), 6);
+    }
+
+    // every ghost ends once per loop, at its end: 11A in 3 steps then every 3, 22A in 2 then every 2
+    const INPUT_TEXT_LOOPS: &str =
+"L
+
+11A = (11B, 11B)
+11B = (11C, 11C)
+11C = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+
+    #[test]
+    fn test_part2_lcm() {
+        let (steps, nodes) = parsing::parse_file(INPUT_TEXT_LOOPS).unwrap();
+        let atlas = Atlas::from(&nodes);
+        let pattern = end_pattern(0, atlas.first_z, &atlas.left_targets, &atlas.right_targets, steps.iter());
+        assert_eq!((pattern.ends, pattern.start, pattern.period), (vec![3], 1, 3));
+        assert_eq!(part2(&steps, &nodes), 6);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(1, 15989), 15989);
+        assert_eq!([15989, 18157, 19783, 14363, 12737, 19241].into_iter().fold(1, lcm), 13830919117339);
     }
 
     #[test]
     fn test_atlas() {
-        let atlas = Atlas::from(parsing::parse_file(INPUT_TEXT_2).1);
+        let atlas = Atlas::from(&parsing::parse_file(INPUT_TEXT_2).unwrap().1);
         assert_eq!(atlas.last_a, 1);
         assert_eq!(atlas.first_z, 6);
         assert_eq!(atlas.left_targets, vec![2, 3, 5, 4, 7, 5, 2, 3]);
@@ -175,8 +243,8 @@ XXX = (XXX, XXX)";
 
     #[test]
     fn test_end_pattern() {
-        let (steps, nodes) = parsing::parse_file(INPUT_TEXT_2);
-        let atlas = Atlas::from(nodes);
+        let (steps, nodes) = parsing::parse_file(INPUT_TEXT_2).unwrap();
+        let atlas = Atlas::from(&nodes);
         let end_pattern = end_pattern(0, atlas.first_z, &atlas.left_targets, &atlas.right_targets, steps.iter());
         assert_eq!(end_pattern.ends, vec![2]);
         assert_eq!(end_pattern.start, 1);
@@ -185,8 +253,8 @@ XXX = (XXX, XXX)";
 
     #[test]
     fn test_end_pattern_real() {
-        let (steps, nodes) = parsing::parse_file(include_str!("input.txt"));
-        let atlas = Atlas::from(nodes);
+        let (steps, nodes) = parsing::parse_file(include_str!("input.txt")).unwrap();
+        let atlas = Atlas::from(&nodes);
         dbg!(&atlas);
         (0..=5).for_each(|i| {
             let end_pattern = end_pattern(i, atlas.first_z, &atlas.left_targets, &atlas.right_targets, steps.iter());
@@ -214,4 +282,45 @@ XXX = (XXX, XXX)";
     x_pos(n) = first + n * period
      */
 
+    /*
+    every ghost walks its own loop: from xxA into a chain of middle nodes, on to xxZ and from there back into the
+    chain, taking the same turn either way, so it first ends after chain + 1 steps and then every chain + 1 steps.
+    the first ghost starts at AAA and ends at ZZZ
+     */
+    fn network() -> impl Strategy<Value = (String, Vec<usize>)> {
+        let steps = prop::collection::vec(prop::sample::select(vec!['L', 'R']), 1..10).prop_map(String::from_iter);
+        (steps, prop::collection::vec(1..8_usize, 1..=4))
+    }
+
+    fn render(steps: &str, chains: &[usize]) -> String {
+        let mut nodes = vec![];
+        for (ghost, &length) in chains.iter().enumerate() {
+            let name = (b'A' + ghost as u8) as char;
+            let start = format!("{}{}A", name, name);
+            let end = if ghost == 0 { "ZZZ".to_string() } else { format!("{}{}Z", name, name) };
+            let chain = (0..length).map(|idx| format!("{}{}M", name, (b'A' + idx as u8) as char)).collect::<Vec<_>>();
+            let next = chain.iter().skip(1).chain([&end]).cloned();
+            nodes.push((start, chain[0].clone()));
+            nodes.extend(chain.iter().cloned().zip(next));
+            nodes.push((end, chain[0].clone()));
+        }
+        nodes.sort();
+        let nodes = nodes.iter().map(|(label, next)| format!("{} = ({}, {})\n", label, next, next)).collect::<String>();
+        format!("{}\n\n{}", steps, nodes)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1((steps, chains) in network()) {
+            let (steps, nodes) = parsing::parse_file(&render(&steps, &chains)).unwrap();
+            prop_assert_eq!(part1(&steps, &nodes), chains[0] as i64 + 1);
+        }
+
+        #[test]
+        fn prop_part2((steps, chains) in network()) {
+            let expected = chains.iter().fold(1, |acc, &length| lcm(acc, length as i64 + 1));
+            let (steps, nodes) = parsing::parse_file(&render(&steps, &chains)).unwrap();
+            prop_assert_eq!(part2(&steps, &nodes), expected);
+        }
+    }
 }
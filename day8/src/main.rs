@@ -1,7 +1,5 @@
-use day8::*;
+use day8::Day8;
 
 fn main() {
-    let input = include_str!("input.txt");
-    println!("part1: {}", part1(input));
-    println!("part2: {}", part2(input));
+    common::run::<Day8>();
 }
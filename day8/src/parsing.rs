@@ -1,36 +1,62 @@
 use crate::{Node, Step};
+use combinators::text::{blank_line, complete, labelled_pair, lines_of, tag};
+use common::ParseError;
+use common::parse::IResult;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alphanumeric1, line_ending};
+use nom::character::complete::alphanumeric1;
 use nom::combinator::value;
-use nom::multi::{many1, separated_list1};
-use nom::{IResult, Parser};
-use nom::sequence::{delimited, separated_pair};
-
-fn parse_node(input: &str) -> IResult<&str, Node> {
-    separated_pair(
-        alphanumeric1, tag(" = "),
-        delimited(
-            tag("("), separated_pair(alphanumeric1, tag(", "), alphanumeric1), tag(")"),
-        ),
-    )(input).map(|(remain, (label, (x, y)))|
-        (remain,
-         Node {
-             label: label.to_string(),
-             left: x.to_string(),
-             right: y.to_string(),
-         })
-    )
+use nom::multi::many1;
+use nom::sequence::separated_pair;
+
+// a node's label and its left and right labels, still pointing into the input
+type Labels<'a> = (&'a str, (&'a str, &'a str));
+
+fn parse_labels(input: &str) -> IResult<&str, Labels<'_>> {
+    labelled_pair(alphanumeric1, alphanumeric1)(input)
+}
+
+fn node((label, (x, y)): Labels) -> Node {
+    Node {
+        label: label.to_string(),
+        left: x.to_string(),
+        right: y.to_string(),
+    }
 }
 
-pub fn parse_file(input: &str) -> (Vec<Step>, Vec<Node>) {
+/*
+the steps and the network, where every left and right label names a node and some nodes end in A and in Z for the
+ghosts to walk between
+ */
+pub fn parse_file(input: &str) -> Result<(Vec<Step>, Vec<Node>), ParseError> {
     let left_or_right = alt((
         value(Step::Left, tag("L")), value(Step::Right, tag("R"))
     ));
     let steps = many1(left_or_right);
-    let nodes = separated_list1(line_ending, parse_node);
 
-    separated_pair(steps, many1(line_ending), nodes).parse(input).unwrap().1
+    let (steps, labels) = complete(separated_pair(steps, blank_line, lines_of(parse_labels)), input)?;
+    let known = |label: &str| labels.iter().any(|(other, _)| *other == label);
+    if let Some(missing) = labels.iter().flat_map(|(_, (left, right))| [left, right]).find(|label| !known(label)) {
+        return Err(ParseError::at(input, missing, "label of a node"));
+    }
+    for end in ["A", "Z"] {
+        if !labels.iter().any(|(label, _)| label.ends_with(end)) {
+            return Err(ParseError::at(input, &input[input.len()..], format!("node ending in {end}")));
+        }
+    }
+    Ok((steps, labels.into_iter().map(node).collect()))
+}
+
+/*
+the whole puzzle, which part1 walks from AAA to ZZZ. the part2 example has neither node, so parse_file can't ask for them
+ */
+pub fn parse_puzzle(input: &str) -> Result<(Vec<Step>, Vec<Node>), ParseError> {
+    let (steps, nodes) = parse_file(input)?;
+    for label in ["AAA", "ZZZ"] {
+        if !nodes.iter().any(|node| node.label == label) {
+            return Err(ParseError::at(input, &input[input.len()..], format!("node {label}")));
+        }
+    }
+    Ok((steps, nodes))
 }
 
 #[cfg(test)]
@@ -39,8 +65,31 @@ mod tests {
 
     #[test]
     fn test_parse_node() {
-        assert_eq!(parse_node("AAA = (BBB, BBB)").unwrap().1,
+        assert_eq!(node(parse_labels("AAA = (BBB, BBB)").unwrap().1),
                    Node { label: "AAA".to_string(), left: "BBB".to_string(), right: "BBB".to_string() });
     }
 
+    #[test]
+    fn test_parse_file_error() {
+        let e = parse_file("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA ZZZ)\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (4, 11, "\", \""));
+    }
+
+    #[test]
+    fn test_unknown_label() {
+        let e = parse_file("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (4, 13, "label of a node"));
+        let e = parse_file("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (5, 1, "node ending in Z"));
+    }
+
+    #[test]
+    fn test_puzzle_ends() {
+        let ghosts = "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n";
+        assert!(parse_file(ghosts).is_ok());
+        let e = parse_puzzle(ghosts).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (5, 1, "node AAA"));
+        assert!(parse_puzzle("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").is_ok());
+    }
+
 }
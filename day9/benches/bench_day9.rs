@@ -0,0 +1,10 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use day9::Day9;
+
+fn bench_day9(c: &mut Criterion) {
+    common::bench::bench_solution::<Day9>(c, include_str!("../src/input.txt"));
+}
+
+criterion_group!(benches, bench_day9);
+criterion_main!(benches);
@@ -1,38 +1,72 @@
+use common::arith::{self, Wide};
+use common::{ParseError, Solution};
 
-pub fn part1(input: &str) -> i32 {
-    input.lines().map(|line| {
-        let series: Vec<i32> = line.split_whitespace().map(|s| s.parse().unwrap()).collect();
-        extrapolate(&series)
-    }).sum()
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    #[cfg(feature = "embedded-input")]
+    const EMBEDDED_INPUT: Option<&'static str> = Some(include_str!("input.txt"));
+    type Input = Vec<Vec<Wide<i32>>>;
+    type Part1 = Wide<i32>;
+    type Part2 = Wide<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_report(input)
+    }
+
+    fn part1(input: &Self::Input) -> Wide<i32> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Wide<i32> {
+        part2(input)
+    }
 }
 
-fn extrapolate(series: &[i32]) -> i32 {
+pub fn parse_report(input: &str) -> Result<Vec<Vec<Wide<i32>>>, ParseError> {
+    input.lines()
+        .map(|line| {
+            // a series needs at least one value to extrapolate from
+            if line.trim().is_empty() {
+                return Err(ParseError::at(input, line, "number"));
+            }
+            line.split_whitespace()
+                .map(|s| s.parse().map_err(|_| ParseError::at(input, s, "number")))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part1(report: &[Vec<Wide<i32>>]) -> Wide<i32> {
+    arith::sum("extrapolated values", report.iter().map(|series| extrapolate(series)))
+}
+
+fn extrapolate(series: &[Wide<i32>]) -> Wide<i32> {
     if series.iter().all(|&n| n == series[0]) {
         return series[0];
     }
 
-    let diffs: Vec<i32> = series.windows(2).map(|w| w[1] - w[0]).collect();
-    extrapolate(&diffs) + series[series.len() - 1]
+    let diffs: Vec<Wide<i32>> = series.windows(2).map(|w| arith::sub("differences", w[1], w[0])).collect();
+    arith::add("next value", extrapolate(&diffs), series[series.len() - 1])
 }
 
-pub fn part2(input: &str) -> i32 {
-    input.lines().map(|line| {
-        let series: Vec<i32> = line.split_whitespace().map(|s| s.parse().unwrap()).collect();
-        extrapolate_backwards(&series)
-    }).sum()
+pub fn part2(report: &[Vec<Wide<i32>>]) -> Wide<i32> {
+    arith::sum("extrapolated values", report.iter().map(|series| extrapolate_backwards(series)))
 }
 
-fn extrapolate_backwards(series: &[i32]) -> i32 {
+fn extrapolate_backwards(series: &[Wide<i32>]) -> Wide<i32> {
     if series.iter().all(|&n| n == series[0]) {
         return series[0];
     }
 
-    let diffs: Vec<i32> = series.windows(2).map(|w| w[1] - w[0]).collect();
-    series[0] - extrapolate_backwards(&diffs)
+    let diffs: Vec<Wide<i32>> = series.windows(2).map(|w| arith::sub("differences", w[1], w[0])).collect();
+    arith::sub("previous value", series[0], extrapolate_backwards(&diffs))
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use super::*;
 
 const TEST_INPUT: &str = "\
@@ -40,23 +74,75 @@ const TEST_INPUT: &str = "\
 1 3 6 10 15 21
 10 13 16 21 30 45
 ";
-const TEST_NEXT_TERMS: [i32; 3] = [18, 28, 68];
+const TEST_NEXT_TERMS: [Wide<i32>; 3] = [18, 28, 68];
 
     #[test]
     fn test_extrapolate() {
-        TEST_INPUT.lines().zip(TEST_NEXT_TERMS.iter()).for_each(|(line, next_term)| {
-            let series: Vec<i32> = line.split_whitespace().map(|s| s.parse().unwrap()).collect();
-            assert_eq!(extrapolate(&series), *next_term);
+        parse_report(TEST_INPUT).unwrap().iter().zip(TEST_NEXT_TERMS.iter()).for_each(|(series, next_term)| {
+            assert_eq!(extrapolate(series), *next_term);
         });
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 114);
+        assert_eq!(part1(&parse_report(TEST_INPUT).unwrap()), 114);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 2);
+        assert_eq!(part2(&parse_report(TEST_INPUT).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse_report("0 3 6\n1 x 6\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "number"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error_empty_line() {
+        let e = parse_report("0 3 6
+
+1 3 6
+").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 1, "number"));
+    }
+
+    /*
+    a polynomial series given by the first value of each row of its difference table, the last row constant,
+    and a length long enough to reach that row
+     */
+    fn series() -> impl Strategy<Value = (Vec<i32>, usize)> {
+        (1..=5_usize).prop_flat_map(|rows| (prop::collection::vec(-10..10_i32, rows), rows + 1..15))
+    }
+
+    // Newton's forward formula, k-th term = sum of binomial(k, j) * first value of row j
+    fn term(differences: &[i32], k: i32) -> i32 {
+        let mut binomial = 1;
+        differences.iter().enumerate().map(|(j, &difference)| {
+            let value = binomial * difference;
+            binomial = binomial * (k - j as i32) / (j as i32 + 1);
+            value
+        }).sum()
+    }
+
+    fn render(report: &[(Vec<i32>, usize)]) -> String {
+        report.iter()
+            .map(|(differences, length)| (0..*length as i32).map(|k| term(differences, k).to_string()).collect::<Vec<_>>().join(" ") + "\n")
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1(report in prop::collection::vec(series(), 1..10)) {
+            let expected = report.iter().map(|(differences, length)| term(differences, *length as i32)).sum::<i32>();
+            prop_assert_eq!(part1(&parse_report(&render(&report)).unwrap()), expected as Wide<i32>);
+        }
+
+        #[test]
+        fn prop_part2(report in prop::collection::vec(series(), 1..10)) {
+            let expected = report.iter().map(|(differences, _)| term(differences, -1)).sum::<i32>();
+            prop_assert_eq!(part2(&parse_report(&render(&report)).unwrap()), expected as Wide<i32>);
+        }
+    }
+}
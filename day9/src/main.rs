@@ -1,7 +1,5 @@
-use day9::*;
+use day9::Day9;
 
 fn main() {
-    let input = include_str!("input.txt");
-    println!("part1: {}", part1(input));
-    println!("part2: {}", part2(input));
+    common::run::<Day9>();
 }
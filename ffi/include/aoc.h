@@ -0,0 +1,26 @@
+/*
+ * the Advent of Code 2023 solvers from libaoc2023, see ffi/src/lib.rs
+ */
+#ifndef AOC2023_H
+#define AOC2023_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_UNKNOWN_DAY 1
+#define AOC_UNKNOWN_PART 2
+#define AOC_NULL_POINTER 3
+#define AOC_INVALID_UTF8 4
+#define AOC_PARSE_ERROR 5
+#define AOC_BUFFER_TOO_SMALL 6
+#define AOC_PANIC 7
+#define AOC_OVERFLOW 8
+
+/*
+ * solve part (1 or 2) of day for the len bytes of UTF-8 at input. the answer, or on AOC_PARSE_ERROR and
+ * AOC_OVERFLOW the error message, is written to out_buf as a NUL terminated string of at most out_len bytes
+ */
+int32_t aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len, uint8_t *out_buf, size_t out_len);
+
+#endif
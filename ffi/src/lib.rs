@@ -0,0 +1,106 @@
+/*
+the solvers behind a C ABI, built as libaoc2023 for harnesses that cannot link Rust. include/aoc.h declares
+what is exported here; nothing unwinds or aborts across it, every failure comes back as one of these codes
+ */
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use common::{Part, SolveError};
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_UNKNOWN_DAY: i32 = 1;
+pub const AOC_UNKNOWN_PART: i32 = 2;
+pub const AOC_NULL_POINTER: i32 = 3;
+pub const AOC_INVALID_UTF8: i32 = 4;
+pub const AOC_PARSE_ERROR: i32 = 5;
+pub const AOC_BUFFER_TOO_SMALL: i32 = 6;
+pub const AOC_PANIC: i32 = 7;
+pub const AOC_OVERFLOW: i32 = 8;
+
+/**
+solve part of day for the len bytes of UTF-8 at input, writing the answer as a NUL terminated string into
+out_buf, which has room for out_len bytes.
+on AOC_PARSE_ERROR and AOC_OVERFLOW out_buf holds the error message instead, cut short to fit;
+on AOC_BUFFER_TOO_SMALL it holds nothing and the answer needs more than out_len bytes.
+
+# Safety
+
+input must point to len readable bytes (it may be null when len is 0) and out_buf to out_len writable bytes
+ */
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(day: u8, part: u8, input: *const u8, len: usize, out_buf: *mut u8, out_len: usize) -> i32 {
+    if (input.is_null() && len > 0) || out_buf.is_null() {
+        return AOC_NULL_POINTER;
+    }
+    let Some(solver) = aoc::solver(day) else { return AOC_UNKNOWN_DAY };
+    let Some(part) = Part::from_number(part) else { return AOC_UNKNOWN_PART };
+    let input = if len == 0 { &[][..] } else { slice::from_raw_parts(input, len) };
+    let Ok(input) = std::str::from_utf8(input) else { return AOC_INVALID_UTF8 };
+    let out = slice::from_raw_parts_mut(out_buf, out_len);
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.solve(input, part))) {
+        Ok(Ok(answer)) if answer.len() < out.len() => {
+            write_truncated(out, &answer);
+            AOC_OK
+        }
+        Ok(Ok(_)) => AOC_BUFFER_TOO_SMALL,
+        Ok(Err(e)) => {
+            write_truncated(out, &e.render("input"));
+            match e {
+                SolveError::Parse(_) => AOC_PARSE_ERROR,
+                SolveError::Overflow(_) => AOC_OVERFLOW,
+            }
+        }
+        Err(_) => AOC_PANIC,
+    }
+}
+
+// as much of text as fits, then NUL; nothing when out has no room even for that
+fn write_truncated(out: &mut [u8], text: &str) {
+    let Some(room) = out.len().checked_sub(1) else { return };
+    let len = text.len().min(room);
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out[len] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use super::*;
+
+    fn solve(day: u8, part: u8, input: &[u8], out_len: usize) -> (i32, String) {
+        let mut out = vec![0xff; out_len.max(1)];
+        let code = unsafe { aoc_solve(day, part, input.as_ptr(), input.len(), out.as_mut_ptr(), out_len) };
+        let text = CStr::from_bytes_until_nul(&out).map(|text| text.to_string_lossy().into_owned()).unwrap_or_default();
+        (code, text)
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(solve(6, 2, b"Time:      7  15   30\nDistance:  9  40  200\n", 64), (AOC_OK, "71503".to_string()));
+        assert_eq!(solve(1, 1, b"1abc2\r\ntreb7uchet\r\n", 3), (AOC_OK, "89".to_string()));
+        assert_eq!(solve(1, 1, b"1abc2\r\ntreb7uchet\r\n", 2).0, AOC_BUFFER_TOO_SMALL);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let (code, message) = solve(9, 1, b"0 3 6\n1 x 6\n", 256);
+        assert_eq!(code, AOC_PARSE_ERROR);
+        assert!(message.starts_with("error: ") && message.contains("--> input:2:3"), "{message}");
+        assert_eq!(solve(9, 1, b"0 3 6\n1 x 6\n", 6), (AOC_PARSE_ERROR, "error".to_string()));
+    }
+
+    #[test]
+    fn test_bad_arguments() {
+        assert_eq!(solve(26, 1, b"", 16).0, AOC_UNKNOWN_DAY);
+        assert_eq!(solve(1, 3, b"", 16).0, AOC_UNKNOWN_PART);
+        assert_eq!(solve(1, 1, b"1abc\xff2\n", 16).0, AOC_INVALID_UTF8);
+        assert_eq!(unsafe { aoc_solve(1, 1, std::ptr::null(), 4, [0u8; 4].as_mut_ptr(), 4) }, AOC_NULL_POINTER);
+        assert_eq!(unsafe { aoc_solve(1, 1, b"12".as_ptr(), 2, std::ptr::null_mut(), 4) }, AOC_NULL_POINTER);
+    }
+
+    #[test]
+    fn test_panic() {
+        // day5 follows the maps from seed until it reaches location, this almanac stops at soil
+        assert_eq!(solve(5, 1, b"seeds: 1\n\nseed-to-soil map:\n0 0 1\n", 16).0, AOC_PANIC);
+    }
+}
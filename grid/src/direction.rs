@@ -0,0 +1,66 @@
+use crate::direction::Direction::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /*
+    clockwise, starting from north
+     */
+    pub const ALL: [Direction; 4] = [North, East, South, West];
+
+    /*
+    (row, col) delta, rows grow southwards
+     */
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            North => (-1, 0),
+            East => (0, 1),
+            South => (1, 0),
+            West => (0, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            North => South,
+            East => West,
+            South => North,
+            West => East,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            North => East,
+            East => South,
+            South => West,
+            West => North,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+        Direction::ALL.into_iter().for_each(|dir| {
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(dir.turn_left().turn_right(), dir);
+        });
+    }
+}
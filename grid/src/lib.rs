@@ -0,0 +1,283 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use common::ParseError;
+
+pub use crate::direction::Direction;
+
+mod direction;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+
+    /*
+    the neighbouring position towards dir, or None when that would fall off the top or left edge
+     */
+    pub fn step(&self, dir: Direction) -> Option<Pos> {
+        self.offset(dir.offset())
+    }
+
+    pub fn offset(&self, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        Some(Pos {
+            row: self.row.checked_add_signed(d_row)?,
+            col: self.col.checked_add_signed(d_col)?,
+        })
+    }
+
+    pub fn manhattan(&self, other: &Pos) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1), (0, 1),
+    (1, -1), (1, 0), (1, 1),
+];
+
+/*
+a rectangular grid stored row by row
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows<R: IntoIterator<Item=T>>(rows: impl IntoIterator<Item=R>) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+            let row_width = cells.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => return Err(ParseError::new(
+                    height + 1, row_width.min(width) + 1, row_expectation(width, row_width)
+                )),
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid { cells, width: width.unwrap_or(0), height })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> where T: Clone {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: &Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: &Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /*
+    the neighbouring position towards dir, if it is still inside the grid
+     */
+    pub fn step(&self, pos: &Pos, dir: Direction) -> Option<Pos> {
+        pos.step(dir).filter(|next| self.contains(next))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        // chunks panics on 0, an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item=Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos { row, col }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /*
+    north, east, south and west neighbours, skipping the ones outside the grid
+     */
+    pub fn neighbours4(&self, pos: &Pos) -> impl Iterator<Item=Pos> + '_ {
+        let pos = *pos;
+        Direction::ALL.into_iter().filter_map(move |dir| self.step(&pos, dir))
+    }
+
+    /*
+    the 4 straight and 4 diagonal neighbours, skipping the ones outside the grid
+     */
+    pub fn neighbours8(&self, pos: &Pos) -> impl Iterator<Item=Pos> + '_ {
+        let pos = *pos;
+        NEIGHBOURS_8.into_iter()
+            .filter_map(move |offset| pos.offset(offset))
+            .filter(|next| self.contains(next))
+    }
+
+    pub fn find<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<Pos> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn find_all<'a, P: Fn(&T) -> bool + 'a>(&'a self, predicate: P) -> impl Iterator<Item=Pos> + 'a {
+        self.iter().filter(move |(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+}
+
+impl Grid<u8> {
+    /*
+    one cell per byte, one row per line
+     */
+    pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        let width = input.lines().next().map(str::len).unwrap_or(0);
+        if let Some(line) = input.lines().find(|line| line.len() != width) {
+            let position = &line[line.len().min(width)..];
+            return Err(ParseError::at(input, position, row_expectation(width, line.len())));
+        }
+        Grid::from_rows(input.lines().map(|line| line.bytes()))
+    }
+}
+
+fn row_expectation(width: usize, row_width: usize) -> String {
+    if row_width > width {
+        "end of row".to_string()
+    } else {
+        format!("{} cells in the row", width)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(&pos).unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(&pos).unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, width, height))
+    }
+}
+
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+S.#
+.#.
+..#
+";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Pos::new(1, 1)], b'#');
+        assert_eq!(grid.row(2), b"..#");
+        assert_eq!(grid.to_string(), TEST_INPUT);
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let e = Grid::parse("..\n...\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "end of row"));
+        let e = Grid::parse("...\n..\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str(), e.found.as_str()), (2, 3, "3 cells in the row", "end of line"));
+        let e = Grid::from_rows([vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(grid.find(|&c| c == b'S'), Some(Pos::new(0, 0)));
+        assert_eq!(grid.find(|&c| c == b'X'), None);
+        assert_eq!(grid.find_all(|&c| c == b'#').collect::<Vec<_>>(),
+                   vec![Pos::new(0, 2), Pos::new(1, 1), Pos::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_neighbours_at_corner() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(grid.neighbours4(&Pos::new(0, 0)).collect::<Vec<_>>(), vec![Pos::new(0, 1), Pos::new(1, 0)]);
+        assert_eq!(grid.neighbours8(&Pos::new(0, 0)).collect::<Vec<_>>(), vec![Pos::new(0, 1), Pos::new(1, 0), Pos::new(1, 1)]);
+        assert_eq!(grid.neighbours8(&Pos::new(2, 2)).collect::<Vec<_>>(), vec![Pos::new(1, 1), Pos::new(1, 2), Pos::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_neighbours_inside() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(grid.neighbours4(&Pos::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(&Pos::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = Grid::parse(TEST_INPUT).unwrap();
+        assert_eq!(grid.step(&Pos::new(0, 0), Direction::North), None);
+        assert_eq!(grid.step(&Pos::new(0, 0), Direction::West), None);
+        assert_eq!(grid.step(&Pos::new(0, 2), Direction::East), None);
+        assert_eq!(grid.step(&Pos::new(0, 0), Direction::South), Some(Pos::new(1, 0)));
+    }
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(Pos::new(6, 1).manhattan(&Pos::new(11, 5)), 9);
+    }
+}
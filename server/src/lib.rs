@@ -0,0 +1,77 @@
+/*
+every solver behind a small HTTP API, for tools that would rather not link Rust:
+
+    POST /day/{n}/part/{p}    body: the puzzle input
+
+answers with aoc's json Report, or {"error": ...} and a 4xx status, 500 when a solver panics
+ */
+use std::panic::{self, AssertUnwindSafe};
+use aoc::report::Report;
+use common::{Part, SolveError};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/*
+answer requests until the server is closed, one at a time
+ */
+pub fn serve(server: &Server) {
+    for request in server.incoming_requests() {
+        respond(request);
+    }
+}
+
+fn respond(mut request: Request) {
+    let mut body = String::new();
+    let (status, json) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => handle(request.method(), request.url(), &body),
+        Err(_) => (400, error("the input is not UTF-8")),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").expect("a valid header");
+    let _ = request.respond(Response::from_string(json).with_status_code(status).with_header(header));
+}
+
+/*
+status and json body for one request
+ */
+pub fn handle(method: &Method, url: &str, body: &str) -> (u16, String) {
+    let Some((day, part)) = route(url) else {
+        return (404, error(&format!("no such endpoint {url}, try POST /day/{{n}}/part/{{p}}")));
+    };
+    if *method != Method::Post {
+        return (405, error(&format!("{url} only answers POST")));
+    }
+    let Some(solver) = day.parse().ok().and_then(aoc::solver) else {
+        return (404, error(&format!("no solution for day {day}")));
+    };
+    let Some(part) = part.parse().ok().and_then(Part::from_number) else {
+        return (404, error(&format!("no part {part}, only 1 and 2")));
+    };
+    // a solver panicking on one input must not take the server down for everyone else
+    let Ok(result) = panic::catch_unwind(AssertUnwindSafe(|| solver.run(body, part))) else {
+        return (500, error(&format!("day{} part{part} panicked on this input", solver.day())));
+    };
+    match result {
+        Ok(run) => (200, Report::new(solver.day(), part, run).to_json()),
+        Err(SolveError::Parse(e)) => (422, json!({
+            "error": e.render("input"),
+            "line": e.line,
+            "column": e.column,
+            "expected": e.expected,
+            "found": e.found,
+        }).to_string()),
+        Err(SolveError::Overflow(e)) => (422, json!({ "error": e.to_string(), "operation": e.operation }).to_string()),
+    }
+}
+
+// (day, part) as written in /day/{n}/part/{p}
+fn route(url: &str) -> Option<(&str, &str)> {
+    let path = url.split('?').next()?;
+    match path.trim_matches('/').split('/').collect::<Vec<_>>().as_slice() {
+        ["day", day, "part", part] => Some((day, part)),
+        _ => None,
+    }
+}
+
+fn error(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
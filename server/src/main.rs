@@ -0,0 +1,24 @@
+use std::{env, process};
+
+const USAGE: &str = "usage: aoc-server [<port>]";
+
+const DEFAULT_PORT: u16 = 2023;
+
+fn main() {
+    common::logging::init();
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let port = match args.as_slice() {
+        [] => DEFAULT_PORT,
+        [port] => port.parse().unwrap_or_else(|_| fail(USAGE)),
+        _ => fail(USAGE),
+    };
+    // loopback only, the solvers are not meant to face the network
+    let server = tiny_http::Server::http(("127.0.0.1", port)).unwrap_or_else(|e| fail(&format!("cannot listen on port {port}: {e}")));
+    eprintln!("listening on http://127.0.0.1:{port}, POST /day/{{n}}/part/{{p}} with the input as body");
+    server::serve(&server);
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(2)
+}
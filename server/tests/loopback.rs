@@ -0,0 +1,69 @@
+/*
+the server on a loopback port, called over HTTP like any other tool would
+ */
+use serde_json::Value;
+
+fn start() -> String {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    std::thread::spawn(move || server::serve(&server));
+    base_url
+}
+
+// status and parsed json body, whatever the status
+fn post(url: &str, body: &str) -> (u16, Value) {
+    let response = match ureq::post(url).send_string(body) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => panic!("{url}: {e}"),
+    };
+    assert_eq!(response.content_type(), "application/json");
+    (response.status(), serde_json::from_str(&response.into_string().unwrap()).unwrap())
+}
+
+#[test]
+fn test_answer() {
+    let base_url = start();
+    let (status, report) = post(&format!("{base_url}/day/6/part/2"), "Time:      7  15   30\r\nDistance:  9  40  200\r\n");
+    assert_eq!(status, 200);
+    assert_eq!((report["day"].as_u64(), report["part"].as_u64()), (Some(6), Some(2)));
+    assert_eq!(report["answer"], "71503");
+    assert!(report["parse_time_ns"].is_u64() && report["solve_time_ns"].is_u64());
+
+    let (status, report) = post(&format!("{base_url}/day/1/part/1"), "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n");
+    assert_eq!((status, report["answer"].as_str()), (200, Some("142")));
+}
+
+#[test]
+fn test_parse_error() {
+    let (status, error) = post(&format!("{}/day/9/part/1", start()), "0 3 6\n1 x 6\n");
+    assert_eq!(status, 422);
+    assert_eq!((error["line"].as_u64(), error["column"].as_u64()), (Some(2), Some(3)));
+    assert!(error["error"].as_str().unwrap().contains("--> input:2:3"), "{error}");
+}
+
+#[test]
+fn test_bad_requests() {
+    let base_url = start();
+    assert_eq!(post(&format!("{base_url}/day/13/part/1"), "").0, 404);
+    assert_eq!(post(&format!("{base_url}/day/1/part/3"), "").0, 404);
+    assert_eq!(post(&format!("{base_url}/days"), "").0, 404);
+    let (status, error) = match ureq::get(&format!("{base_url}/day/1/part/1")).call() {
+        Err(ureq::Error::Status(status, response)) => (status, response.into_string().unwrap()),
+        other => panic!("{other:?}"),
+    };
+    assert_eq!(status, 405);
+    assert_eq!(error, r#"{"error":"/day/1/part/1 only answers POST"}"#);
+}
+
+#[test]
+fn test_panic() {
+    let base_url = start();
+    // day5 follows the maps from seed until it reaches location, this almanac stops at soil
+    let (status, error) = post(&format!("{base_url}/day/5/part/1"), "seeds: 1\n\nseed-to-soil map:\n0 0 1\n");
+    assert_eq!(status, 500);
+    assert_eq!(error["error"], "day5 part1 panicked on this input");
+
+    let (status, report) = post(&format!("{base_url}/day/1/part/1"), "1abc2\ntreb7uchet\n");
+    assert_eq!((status, report["answer"].as_str()), (200, Some("89")));
+}